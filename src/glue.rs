@@ -1,7 +1,10 @@
 //! "Glue" APIs that allow working with select other crates.
 
+#[cfg(any(feature = "regex", feature = "glob"))] pub(crate) mod bounded_cache;
 #[cfg(feature = "regex"   )] pub mod regex;
 #[cfg(feature = "regex"   )] pub use regex::*;
+#[cfg(feature = "regex"   )] pub mod regex_set;
+#[cfg(feature = "regex"   )] pub use regex_set::*;
 #[cfg(feature = "glob"    )] pub mod glob;
 #[cfg(feature = "glob"    )] pub use glob::*;
 #[cfg(feature = "commands")] pub mod command;
@@ -15,9 +18,15 @@
 #[cfg(feature = "http"    )] pub(crate) mod method;
 #[cfg(feature = "http"    )] pub mod advanced_http;
 #[cfg(feature = "http"    )] pub use advanced_http::*;
+#[cfg(feature = "async"   )] pub mod async_http;
+#[cfg(feature = "async"   )] pub use async_http::*;
 #[cfg(feature = "cache"   )] pub mod caching;
 #[cfg(feature = "cache"   )] pub use caching::*;
 #[cfg(feature = "base64"  )] pub mod base64;
 #[cfg(feature = "base64"  )] pub use base64::*;
+#[cfg(feature = "hash"    )] pub mod hash;
+#[cfg(feature = "hash"    )] pub use hash::*;
+#[cfg(feature = "http"    )] pub mod rate_limit;
+#[cfg(feature = "http"    )] pub use rate_limit::*;
 #[cfg(feature = "custom"  )] pub mod fn_wrapper;
 #[cfg(feature = "custom"  )] pub use fn_wrapper::*;
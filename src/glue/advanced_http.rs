@@ -3,6 +3,8 @@
 //! Enabled by the `http` feature flag.
 
 use std::collections::HashMap;
+#[cfg(feature = "async")]
+use std::borrow::Cow;
 
 use url::Url;
 use serde::{Deserialize, Serialize};
@@ -30,6 +32,8 @@ pub struct RequestConfig {
     #[serde(default, skip_serializing_if = "is_default", with = "method")]
     pub method: Method,
     /// The headers to send in the request in addition to the default headers provided by [`Params::http_client_config`] and [`Self::client_config_diff`].
+    ///
+    /// Overwrites headers with the same name from [`HttpClientConfig::dynamic_headers`].
     /// 
     /// If a call to [`StringSource::get`] returns [`None`], that header is omitted from the request. For a header with an empty value, use [`StringSource::NoneToEmptyString`].
     /// 
@@ -75,6 +79,9 @@ pub enum RequestConfigError {
     /// Returned when a [`ResponseHandlerError`] is encountered.
     #[error(transparent)]
     ResponseHandlerError(#[from] ResponseHandlerError),
+    /// Returned when a [`MakeDynamicHeaderError`] is encountered.
+    #[error(transparent)]
+    MakeDynamicHeaderError(#[from] MakeDynamicHeaderError),
     /// Returned when making the headermap fails.
     #[error("Couldn't make the HeaderMap. Name error: {name:?}. Value error: {value:?}.")]
     MakeHeaderMapError {
@@ -93,8 +100,12 @@ impl From<StringSourceError> for RequestConfigError {
 
 impl RequestConfig {
     /// Makes a [`reqwest::blocking::RequestBuilder`].
+    ///
+    /// Before returning, calls [`JobStateView::wait_for_rate_limit`] so the returned request can be sent immediately.
     /// # Errors
-    /// If the call to [`JobStateView::http_client`] returns an error, that error is returned.
+    /// If the call to [`HttpClientConfig::client`] returns an error, that error is returned.
+    ///
+    /// If the call to [`JobStateView::dynamic_headers`] returns an error, that error is returned.
     /// 
     /// If any of the header names in [`Self::headers`] are, once [`str::to_lowercase`] is applied, an invalid [`HeaderName`], the error is returned in a [`RequestConfigError::MakeHeaderMapError`].
     /// 
@@ -104,13 +115,30 @@ impl RequestConfig {
     /// 
     /// If the call to [`RequestBody::apply`] returns an error, that error is returned.
     pub fn make(&self, job_state: &JobStateView) -> Result<reqwest::blocking::RequestBuilder, RequestConfigError> {
-        let mut ret=job_state.http_client(self.client_config_diff.as_ref())?
+        let url = Url::parse(get_str!(self.url, job_state, RequestConfigError))?;
+        let http_client_config = job_state.http_client_config(self.client_config_diff.as_ref());
+        let mut ret=http_client_config.client()?
             .request(
                 self.method.clone(),
-                Url::parse(get_str!(self.url, job_state, RequestConfigError))?,
+                url.clone(),
             );
 
-        ret = ret.headers(self.headers
+        ret = ret.headers(job_state.dynamic_headers(&http_client_config)?);
+        ret = ret.headers(self.header_map(job_state)?);
+        if let Some(body) = &self.body {ret=body.apply(ret, job_state)?;}
+        job_state.wait_for_rate_limit(url.as_str(), &http_client_config);
+        Ok(ret)
+    }
+
+    /// Makes the [`HeaderMap`] from [`Self::headers`].
+    /// # Errors
+    /// If any of the header names in [`Self::headers`] are, once [`str::to_lowercase`] is applied, an invalid [`HeaderName`], the error is returned in a [`RequestConfigError::MakeHeaderMapError`].
+    /// 
+    /// If any of the calls to [`StringSource::get`] from [`Self::headers`] return an error, that error is returned.
+    /// 
+    /// If any of the calls to [`StringSource::get`] return an invalid [`HeaderValue`], the error is returned in a [`RequestConfigError::MakeHeaderMapError`].
+    fn header_map(&self, job_state: &JobStateView) -> Result<HeaderMap, RequestConfigError> {
+        self.headers
             .iter()
             .map(
                 |(k, v)| Ok(v.get(job_state)?
@@ -128,9 +156,7 @@ impl RequestConfig {
                 Ok((Err(k), Err(v))) => Err(RequestConfigError::MakeHeaderMapError { name: Some(k), value: Some(v) }),
                 Err(e) => Err(RequestConfigError::StringSourceError(Box::new(e)))
             })
-            .collect::<Result<HeaderMap<_>, _>>()?);
-        if let Some(body) = &self.body {ret=body.apply(ret, job_state)?;}
-        Ok(ret)
+            .collect::<Result<HeaderMap<_>, _>>()
     }
 
     /// Sends the request then uses [`Self::response_handler`] to get a [`String`] from the [`reqwest::blocking::Response`].
@@ -143,6 +169,69 @@ impl RequestConfig {
     pub fn response(&self, job_state: &JobStateView) -> Result<String, RequestConfigError> {
         Ok(self.response_handler.handle(self.make(job_state)?.send()?, job_state)?)
     }
+
+    /// [`Self::make`] but makes an async [`reqwest::RequestBuilder`] from the client built from [`HttpClientConfig::async_client`].
+    ///
+    /// Unlike [`Self::make`], doesn't wait for [`JobStateView::wait_for_rate_limit`], as that would block the async runtime. [`Self::response_async`] waits for [`JobStateView::wait_for_rate_limit_async`] instead.
+    /// # Errors
+    /// If the call to [`HttpClientConfig::async_client`] returns an error, that error is returned.
+    ///
+    /// Otherwise has the same errors as [`Self::make`].
+    #[cfg(feature = "async")]
+    pub fn make_async(&self, job_state: &JobStateView) -> Result<reqwest::RequestBuilder, RequestConfigError> {
+        Ok(self.make_async_with_config(job_state, HttpClientConfig::async_client)?.0)
+    }
+
+    /// [`Self::make_async`] but gets the client from `make_client` and also returns the [`HttpClientConfig`] used, for [`JobStateView::wait_for_rate_limit_async`].
+    /// # Errors
+    /// If the call to `make_client` returns an error, that error is returned.
+    ///
+    /// Otherwise has the same errors as [`Self::make_async`].
+    #[cfg(feature = "async")]
+    pub(crate) fn make_async_with_config<'a>(&self, job_state: &JobStateView<'a>, make_client: impl FnOnce(&HttpClientConfig) -> reqwest::Result<reqwest::Client>) -> Result<(reqwest::RequestBuilder, Cow<'a, HttpClientConfig>), RequestConfigError> {
+        let url = Url::parse(get_str!(self.url, job_state, RequestConfigError))?;
+        let http_client_config = job_state.http_client_config(self.client_config_diff.as_ref());
+        let mut ret=make_client(&http_client_config)?
+            .request(
+                self.method.clone(),
+                url,
+            );
+
+        ret = ret.headers(job_state.dynamic_headers(&http_client_config)?);
+        ret = ret.headers(self.header_map(job_state)?);
+        if let Some(body) = &self.body {ret=body.apply_async(ret, job_state)?;}
+        Ok((ret, http_client_config))
+    }
+
+    /// [`Self::response`] but sends the request asynchronously.
+    ///
+    /// Everything that isn't waiting for [`JobStateView::wait_for_rate_limit_async`], sending the request, or reading the response is done before the first `.await`.
+    ///
+    /// Usually used via [`AsyncHttpRunner::response`] instead of directly, which also reuses clients between requests.
+    /// # Errors
+    /// If the call to [`Self::make_async`] returns an error, that error is returned.
+    /// 
+    /// If the call to [`reqwest::RequestBuilder::send`] returns an error, that error is returned.
+    /// 
+    /// If the call to [`ResponseHandler::handle_async`] returns an error, that error is returned.
+    #[cfg(feature = "async")]
+    pub async fn response_async(&self, job_state: &JobStateView<'_>) -> Result<String, RequestConfigError> {
+        let (request, http_client_config) = self.make_async_with_config(job_state, HttpClientConfig::async_client)?;
+        let (client, request) = request.build_split();
+        let request = request?;
+        job_state.wait_for_rate_limit_async(request.url().as_str(), &http_client_config).await;
+        self.send_async(client, request, job_state).await
+    }
+
+    /// Sends `request` with `client` then uses [`Self::response_handler`] to get a [`String`] from the [`reqwest::Response`].
+    /// # Errors
+    /// If the call to [`reqwest::Client::execute`] returns an error, that error is returned.
+    ///
+    /// If the call to [`ResponseHandler::handle_async`] returns an error, that error is returned.
+    #[cfg(feature = "async")]
+    pub(crate) async fn send_async(&self, client: reqwest::Client, request: reqwest::Request, job_state: &JobStateView<'_>) -> Result<String, RequestConfigError> {
+        Ok(self.response_handler.handle_async(client.execute(request).await?, job_state).await?)
+    }
 }
 
 /// The ways one can set the body in an HTTP request.
@@ -201,6 +290,26 @@ impl RequestBody {
             Self::Json(json) => request.json(&json.make(job_state)?)
         })
     }
+
+    /// [`Self::apply`] but for the async [`reqwest::RequestBuilder`].
+    /// # Errors
+    /// See each of [`Self`]'s variant's documentation for details.
+    #[cfg(feature = "async")]
+    pub fn apply_async(&self, request: reqwest::RequestBuilder, job_state: &JobStateView) -> Result<reqwest::RequestBuilder, RequestBodyError> {
+        Ok(match self {
+            Self::Text(source) => request.body(get_string!(source, job_state, RequestBodyError)),
+            Self::Form(map) => request.form(&map.iter()
+                .map(|(k, v_source)| v_source.get(job_state)
+                    .map(|maybe_v| maybe_v
+                        .map(|v| (k, v.into_owned()))
+                    )
+                )
+                .collect::<Result<Option<HashMap<_, _>>, _>>()?
+                .ok_or(RequestBodyError::StringSourceIsNone)?
+            ),
+            Self::Json(json) => request.json(&json.make(job_state)?)
+        })
+    }
 }
 
 /// The ways one can get a [`String`] from a [`reqwest::blocking::Response`].
@@ -273,4 +382,20 @@ impl ResponseHandler {
             }
         })
     }
+
+    /// [`Self::handle`] but for the async [`reqwest::Response`].
+    /// # Errors
+    /// See each of [`Self`]'s variant's documentation for details.
+    #[cfg(feature = "async")]
+    pub async fn handle_async(&self, response: reqwest::Response, job_state: &JobStateView<'_>) -> Result<String, ResponseHandlerError> {
+        Ok(match self {
+            Self::Body => response.text().await?,
+            Self::Header(source) => response.headers().get(get_str!(source, job_state, ResponseHandlerError)).ok_or(ResponseHandlerError::HeaderNotFound)?.to_str()?.to_string(),
+            Self::Url => response.url().as_str().to_string(),
+            Self::Cookie(source) => {
+                let name = get_string!(source, job_state, ResponseHandlerError);
+                response.cookies().find(|cookie| cookie.name()==name).ok_or(ResponseHandlerError::CookieNotFound)?.value().to_string()
+            }
+        })
+    }
 }
@@ -0,0 +1,164 @@
+//! Provides [`AsyncHttpRunner`] which sends HTTP requests on a shared async runtime.
+//!
+//! Enabled by the `async` feature flag.
+//!
+//! [`Rule`]s, [`Condition`]s, [`Mapper`]s, and [`StringSource`]s are all synchronous, so the async code is bridged in by blocking on a shared runtime only for the part of a request that's actually waiting on the network.
+//!
+//! Compared to the blocking API this means
+//!
+//! - [`reqwest::blocking::Client`]s each spawn their own runtime thread, whereas the [`reqwest::Client`]s made by [`AsyncHttpRunner::client`] all share [`AsyncHttpRunner`]'s runtime and are reused between requests, and
+//! - The amount of requests in flight at once is bounded by [`AsyncHttpRunner::max_in_flight`] regardless of how many threads are cleaning URLs.
+//!
+//! Currently only [`StringSource::HttpRequest`] uses this.
+
+#![allow(dead_code, reason = "Public API partially not used by the CLI.")]
+
+use std::future::Future;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use tokio::runtime::Runtime;
+use tokio::sync::Semaphore;
+
+use crate::types::*;
+use crate::glue::*;
+
+/// The default value of [`AsyncHttpRunner::max_in_flight`].
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 64;
+
+/// The maximum amount of [`reqwest::Client`]s kept by [`AsyncHttpRunner::client`].
+pub const MAX_CACHED_CLIENTS: usize = 16;
+
+/// The [`AsyncHttpRunner`] used by [`AsyncHttpRunner::global`].
+static GLOBAL_ASYNC_HTTP_RUNNER: OnceLock<AsyncHttpRunner> = OnceLock::new();
+
+/// Sends HTTP requests on a shared async runtime with a bounded amount of requests in flight.
+#[derive(Debug)]
+pub struct AsyncHttpRunner {
+    /// The runtime to run requests on.
+    runtime: Runtime,
+    /// Limits how many requests can be in flight at once.
+    semaphore: Semaphore,
+    /// The maximum amount of requests that can be in flight at once.
+    max_in_flight: usize,
+    /// The clients made by [`Self::client`], most recently made last.
+    clients: Mutex<Vec<(HttpClientConfig, reqwest::Client)>>
+}
+
+impl AsyncHttpRunner {
+    /// Makes a new [`Self`] with a multi-threaded runtime that allows at most `max_in_flight` requests at once.
+    ///
+    /// If `max_in_flight` is zero, uses [`DEFAULT_MAX_IN_FLIGHT`].
+    /// # Errors
+    /// If the call to [`tokio::runtime::Builder::build`] returns an error, that error is returned.
+    pub fn new(max_in_flight: usize) -> std::io::Result<Self> {
+        let max_in_flight = if max_in_flight == 0 {DEFAULT_MAX_IN_FLIGHT} else {max_in_flight};
+        Ok(Self {
+            runtime: tokio::runtime::Builder::new_multi_thread().enable_all().build()?,
+            semaphore: Semaphore::new(max_in_flight),
+            max_in_flight,
+            clients: Default::default()
+        })
+    }
+
+    /// Gets the [`Self`] used by [`StringSource::HttpRequest`].
+    ///
+    /// If not already set by [`Self::set_global`], makes one with [`DEFAULT_MAX_IN_FLIGHT`].
+    /// # Panics
+    /// If making the runtime fails, panics.
+    pub fn global() -> &'static Self {
+        GLOBAL_ASYNC_HTTP_RUNNER.get_or_init(|| Self::new(DEFAULT_MAX_IN_FLIGHT).expect("Making the async runtime to work."))
+    }
+
+    /// Sets the [`Self`] returned by [`Self::global`].
+    /// # Errors
+    /// If [`Self::global`] was already set or used, returns `runner` in an error.
+    pub fn set_global(runner: Self) -> Result<(), Box<Self>> {
+        GLOBAL_ASYNC_HTTP_RUNNER.set(runner).map_err(Box::new)
+    }
+
+    /// The maximum amount of requests that can be in flight at once.
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    /// Gets a [`reqwest::Client`] made with `config`, reusing the one made last time if `config` is the same.
+    ///
+    /// Reusing clients lets requests share connections and avoids setting up TLS every time.
+    ///
+    /// Only the [`MAX_CACHED_CLIENTS`] most recently made clients are kept.
+    /// # Errors
+    /// If the call to [`HttpClientConfig::async_client`] returns an error, that error is returned.
+    pub fn client(&self, config: &HttpClientConfig) -> reqwest::Result<reqwest::Client> {
+        // The list is always left in a valid state, so poisoning doesn't matter.
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((_, client)) = clients.iter().find(|(x, _)| x == config) {
+            return Ok(client.clone());
+        }
+        let client = config.async_client()?;
+        if clients.len() >= MAX_CACHED_CLIENTS {
+            clients.remove(0);
+        }
+        clients.push((config.clone(), client.clone()));
+        Ok(client)
+    }
+
+    /// Blocks the current thread on `future` once there's room for another request.
+    ///
+    /// Must not be called from inside an async context.
+    /// # Panics
+    /// If called from inside an async context, panics.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::glue::*;
+    /// let runner = AsyncHttpRunner::new(2).unwrap();
+    ///
+    /// assert_eq!(runner.max_in_flight(), 2);
+    /// assert_eq!(runner.block_on(async {1 + 1}), 2);
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(async {
+            let _permit = self.semaphore.acquire().await.expect("The semaphore to never be closed.");
+            future.await
+        })
+    }
+
+    /// Blocks the current thread on [`RequestConfig::response_async`].
+    ///
+    /// Waiting for [`JobStateView::wait_for_rate_limit_async`] is done before taking up one of the [`Self::max_in_flight`] slots.
+    ///
+    /// The client is from [`Self::client`], so requests with the same [`HttpClientConfig`] share it.
+    /// # Errors
+    /// If the call to [`RequestConfig::response_async`] returns an error, that error is returned.
+    /// # Panics
+    /// If called from inside an async context, panics.
+    pub fn response(&self, config: &RequestConfig, job_state: &JobStateView) -> Result<String, RequestConfigError> {
+        self.runtime.block_on(async {
+            let (request, http_client_config) = config.make_async_with_config(job_state, |http_client_config| self.client(http_client_config))?;
+            let (client, request) = request.build_split();
+            let request = request?;
+            job_state.wait_for_rate_limit_async(request.url().as_str(), &http_client_config).await;
+            let _permit = self.semaphore.acquire().await.expect("The semaphore to never be closed.");
+            config.send_async(client, request, job_state).await
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clients_are_reused() {
+        let runner = AsyncHttpRunner::new(1).unwrap();
+        let config = HttpClientConfig::default();
+        let other = HttpClientConfig {https_only: true, ..Default::default()};
+
+        runner.client(&config).unwrap();
+        runner.client(&config).unwrap();
+        assert_eq!(runner.clients.lock().unwrap().len(), 1);
+
+        runner.client(&other).unwrap();
+        assert_eq!(runner.clients.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,70 @@
+//! Provides [`BoundedCache`], a small least recently used cache used to share compiled regexes and parsed globs.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::borrow::Borrow;
+
+/// A map that holds at most [`Self::capacity`] entries, evicting the least recently used one when full.
+///
+/// Lookups and updates are `O(1)`, but inserting into a full cache scans every entry to find the one to evict.
+/// This is fine for the small capacities it's used with, and inserting means something just got compiled, which is much slower anyway.
+#[derive(Debug)]
+pub(crate) struct BoundedCache<K, V> {
+    /// The entries and the value of [`Self::clock`] when each was last used.
+    entries: HashMap<K, (V, u64)>,
+    /// Incremented on every use to order entries by recency.
+    clock: u64,
+    /// The maximum amount of entries.
+    capacity: usize
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> BoundedCache<K, V> {
+    /// Makes an empty [`Self`] that holds at most `capacity` entries.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            clock: 0,
+            capacity
+        }
+    }
+
+    /// Gets a clone of the value for `key` and marks it as the most recently used.
+    pub(crate) fn get<Q: Eq + Hash + ?Sized>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        self.clock = self.clock.wrapping_add(1);
+        let clock = self.clock;
+        self.entries.get_mut(key).map(|(value, last_used)| {
+            *last_used = clock;
+            value.clone()
+        })
+    }
+
+    /// Inserts `value` for `key`, first evicting the least recently used entry if [`Self`] is full.
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            let oldest = self.entries.iter().min_by_key(|(_, (_, last_used))| *last_used).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock = self.clock.wrapping_add(1);
+        self.entries.insert(key, (value, self.clock));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = BoundedCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get("a"), Some(1));
+        cache.insert("c", 3);
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("c"), Some(3));
+        assert_eq!(cache.entries.len(), 2);
+    }
+}
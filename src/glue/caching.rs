@@ -2,7 +2,7 @@
 //! 
 //! Enabled by the `caching` feature flag.
 
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::str::FromStr;
 use std::cell::OnceCell;
 use std::path::Path;
@@ -193,9 +193,6 @@ impl From<CachePath> for InnerCache {
 /// The enum of errors [`Cache::read`] and [`InnerCache::read`] can return.
 #[derive(Debug, Error)]
 pub enum ReadFromCacheError {
-    /// Returned when the inner [`Mutex`] is poisoned.
-    #[error("{0}")]
-    MutexPoisonError(String),
     /// Returned when a [`diesel::result::Error`] is encountered.
     #[error(transparent)]
     DieselError(#[from] diesel::result::Error),
@@ -207,9 +204,6 @@ pub enum ReadFromCacheError {
 /// The enum of errors [`Cache::write`] and [`InnerCache::write`] can return.
 #[derive(Debug, Error)]
 pub enum WriteToCacheError {
-    /// Returned when the inner [`Mutex`] is poisoned.
-    #[error("{0}")]
-    MutexPoisonError(String),
     /// Returned when a [`diesel::result::Error`] is encountered.
     #[error(transparent)]
     DieselError(#[from] diesel::result::Error),
@@ -219,22 +213,41 @@ pub enum WriteToCacheError {
 }
 
 impl Cache {
+    /// Locks the inner [`Mutex`].
+    ///
+    /// If the [`Mutex`] is poisoned, the poison is cleared and the [`InnerCache`] is used anyway.
+    ///
+    /// This is done because a thread panicking while holding the lock very rarely leaves the [`SqliteConnection`] unusable for new queries, and erroring forever would effectively disable caching for the rest of the process.
+    fn lock(&self) -> MutexGuard<'_, InnerCache> {
+        self.0.lock().unwrap_or_else(|e| {
+            self.0.clear_poison();
+            e.into_inner()
+        })
+    }
+
+    /// If the inner [`Mutex`] is poisoned, clear the poison.
+    ///
+    /// [`Self::read`] and [`Self::write`] already do this automatically.
+    pub fn clear_poison(&self) {
+        self.0.clear_poison();
+    }
+
     /// Reads a string from the cache.
+    ///
+    /// If the inner [`Mutex`] is poisoned, the poison is cleared and the read continues as normal.
     /// # Errors
-    /// If the call to [`Mutex::lock`] returns an error, that error is returned.
-    /// 
     /// If the call to [`InnerCache::read`] returns an error, that error is returned.
     pub fn read(&self, category: &str, key: &str) -> Result<Option<Option<String>>, ReadFromCacheError> {
-        self.0.lock().map_err(|e| ReadFromCacheError::MutexPoisonError(e.to_string()))?.read(category, key)
+        self.lock().read(category, key)
     }
 
     /// Writes a string to the cache.
+    ///
+    /// If the inner [`Mutex`] is poisoned, the poison is cleared and the write continues as normal.
     /// # Errors
-    /// If the call to [`Mutex::lock`] returns an error, that error is returned.
-    /// 
     /// If the call to [`InnerCache::write`] returns an error, that error is returned.
     pub fn write(&self, category: &str, key: &str, value: Option<&str>) -> Result<(), WriteToCacheError> {
-        self.0.lock().map_err(|e| WriteToCacheError::MutexPoisonError(e.to_string()))?.write(category, key, value)
+        self.lock().write(category, key, value)
     }
 }
 
@@ -346,3 +359,42 @@ impl From<InnerCache> for (CachePath, OnceCell<SqliteConnection>) {
         (value.path, value.connection)
     }
 }
+
+#[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poisoned_mutex_recovery_test() {
+        let cache = Cache::from(CachePath::Memory);
+        cache.write("category", "key", Some("value")).unwrap();
+
+        let cache2 = cache.clone();
+        std::thread::spawn(move || {
+            let _guard = cache2.0.lock().unwrap();
+            panic!("Poisoning the cache's mutex.");
+        }).join().unwrap_err();
+        assert!(cache.0.is_poisoned());
+
+        assert_eq!(cache.read("category", "key").unwrap(), Some(Some("value".into())));
+        assert!(!cache.0.is_poisoned());
+        cache.write("category", "key2", None).unwrap();
+        assert_eq!(cache.read("category", "key2").unwrap(), Some(None));
+    }
+
+    #[test]
+    fn clear_poison_test() {
+        let cache = Cache::from(CachePath::Memory);
+
+        let cache2 = cache.clone();
+        std::thread::spawn(move || {
+            let _guard = cache2.0.lock().unwrap();
+            panic!("Poisoning the cache's mutex.");
+        }).join().unwrap_err();
+        assert!(cache.0.is_poisoned());
+
+        cache.clear_poison();
+        assert!(!cache.0.is_poisoned());
+    }
+}
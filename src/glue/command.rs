@@ -2,7 +2,7 @@
 //! 
 //! Enabled by the `commands` feature flag.
 
-use std::process::{Command, Stdio};
+use std::process::{Command, Child, ExitStatus, Stdio};
 use std::io::Write;
 use std::path::PathBuf;
 use std::str::{from_utf8, FromStr};
@@ -131,34 +131,109 @@ impl CommandConfig {
         PathBuf::from(&self.program).exists() || which(&self.program).is_ok()
     }
 
+    /// Spawns the command from [`Self::make_command`] with the specified STDOUT and, if [`Self::stdin`] is [`Some`], writes its value to the command's STDIN.
+    ///
+    /// The child's STDIN is closed before returning so commands that read until EOF don't hang.
+    /// # Errors
+    /// If the call to [`Self::make_command`] returns an error, that error is returned.
+    ///
+    /// If the call to [`Command::spawn`] returns an error, that error is returned.
+    ///
+    /// If the call to [`StringSource::get`] returns an error, that error is returned.
+    ///
+    /// If the call to [`StringSource::get`] returns [`None`], returns the error [`CommandError::StringSourceIsNone`].
+    ///
+    /// If the call to [`std::process::ChildStdin::write_all`] returns an error, that error is returned.
+    fn spawn(&self, job_state: &JobStateView, stdout: Stdio) -> Result<Child, CommandError> {
+        // https://stackoverflow.com/a/49597789/10720231
+        let mut command = self.make_command(job_state)?;
+        command.stdout(stdout);
+        command.stderr(Stdio::null());
+        match &self.stdin {
+            Some(stdin) => {
+                let stdin = get_string!(stdin, job_state, CommandError);
+                command.stdin(Stdio::piped());
+                let mut child = command.spawn()?;
+                if let Some(mut child_stdin) = child.stdin.take() {
+                    child_stdin.write_all(stdin.as_bytes())?;
+                }
+                Ok(child)
+            },
+            None => Ok(command.spawn()?)
+        }
+    }
+
     /// Runs the command and gets the exit code.
+    ///
+    /// If [`Self::stdin`] is [`Some`], its value is written to the command's STDIN.
     /// # Errors
+    /// If the call to [`Self::spawn`] returns an error, that error is returned.
+    ///
+    /// If the call to [`std::process::Child::wait`] returns an error, that error is returned.
+    ///
     /// If the command returns no exit code, returns the error [`CommandError::SignalTermination`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use url_cleaner::glue::CommandConfig;
+    /// url_cleaner::job_state!(job_state; url = "https://example.com/a";);
+    ///
+    /// let command = CommandConfig {
+    ///     program: "/bin/sh".into(),
+    ///     args: vec!["-c".into(), "grep -q example".into()],
+    ///     current_dir: None,
+    ///     envs: Default::default(),
+    ///     stdin: Some(StringSource::Part(UrlPart::Whole))
+    /// };
+    ///
+    /// assert_eq!(command.exit_code(&job_state.to_view()).unwrap(), 0);
+    /// *job_state.url = BetterUrl::parse("https://other.com").unwrap();
+    /// assert_eq!(command.exit_code(&job_state.to_view()).unwrap(), 1);
+    /// ```
     pub fn exit_code(&self, job_state: &JobStateView) -> Result<i32, CommandError> {
-        self.make_command(job_state)?.status()?.code().ok_or(CommandError::SignalTermination)
+        self.spawn(job_state, Stdio::null())?.wait()?.code().ok_or(CommandError::SignalTermination)
     }
 
     /// Run the command from [`Self::make_command`] and returns the STDOUT.
+    ///
+    /// If [`Self::stdin`] is [`Some`], its value is written to the command's STDIN.
     /// # Errors
-    /// If `stdin` is `Some` and the calls to [`Command::spawn`], [`std::process::ChildStdin::write_all`], or [`std::process::Child::wait_with_output`] returns an error, that error is returned.
-    /// 
-    /// If `stdin` is `None` and the call to [`Command::output`] returns an error, that error is returned.
-    #[allow(clippy::missing_panics_doc, reason = "Shouldn't ever panic.")]
+    /// If the call to [`Self::spawn`] returns an error, that error is returned.
+    ///
+    /// If the call to [`std::process::Child::wait_with_output`] returns an error, that error is returned.
+    ///
+    /// If the STDOUT isn't valid UTF-8, returns the error [`CommandError::Utf8Error`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use url_cleaner::glue::CommandConfig;
+    /// url_cleaner::job_state!(job_state; url = "https://example.com/a";);
+    ///
+    /// let command = CommandConfig {
+    ///     program: "/bin/cat".into(),
+    ///     args: Vec::new(),
+    ///     current_dir: None,
+    ///     envs: Default::default(),
+    ///     stdin: Some(StringSource::Part(UrlPart::Whole))
+    /// };
+    ///
+    /// assert_eq!(command.output(&job_state.to_view()).unwrap(), "https://example.com/a");
+    /// assert_eq!(command.get_url(&job_state.to_view()).unwrap().as_str(), "https://example.com/a");
+    /// ```
     pub fn output(&self, job_state: &JobStateView) -> Result<String, CommandError> {
-        // https://stackoverflow.com/a/49597789/10720231
-        let mut command = self.make_command(job_state)?;
-        command.stdout(Stdio::piped());
-        command.stderr(Stdio::null());
-        let child = if let Some(stdin) = &self.stdin {
-            command.stdin(Stdio::piped());
-            let mut child=command.spawn()?;
-            let child_stdin=child.stdin.as_mut().expect("The STDIN just set to be available."); // This never panics.
-            child_stdin.write_all(get_string!(stdin, job_state, CommandError).as_bytes())?;
-            child
-        } else {
-            command.spawn()?
-        };
-        Ok(from_utf8(&child.wait_with_output()?.stdout)?.to_string())
+        Ok(self.output_and_status(job_state)?.0)
+    }
+
+    /// [`Self::output`] but also returns the command's [`ExitStatus`].
+    /// # Errors
+    /// If the call to [`Self::spawn`] returns an error, that error is returned.
+    ///
+    /// If the call to [`std::process::Child::wait_with_output`] returns an error, that error is returned.
+    ///
+    /// If the STDOUT isn't valid UTF-8, returns the error [`CommandError::Utf8Error`].
+    pub fn output_and_status(&self, job_state: &JobStateView) -> Result<(String, ExitStatus), CommandError> {
+        let output = self.spawn(job_state, Stdio::piped())?.wait_with_output()?;
+        Ok((from_utf8(&output.stdout)?.to_string(), output.status))
     }
 
     /// Runs the command, gets the STDOUT, trims trailing newlines and carriage returns form the output using [`str::trim_end_matches`], then extracts the URL.
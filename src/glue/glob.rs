@@ -4,8 +4,9 @@
 
 use std::str::FromStr;
 use std::path::Path;
+use std::sync::{Mutex, LazyLock, PoisonError};
 
-use glob::{Pattern, MatchOptions};
+use glob::{Pattern, PatternError, MatchOptions};
 use serde::{
     Serialize, Deserialize,
     ser::Serializer,
@@ -14,8 +15,41 @@ use serde::{
 
 use crate::types::*;
 use crate::util::*;
+use super::bounded_cache::BoundedCache;
+
+/// The parsed [`Pattern`]s of every glob string parsed by [`GlobWrapper::from_str`] and deserializing [`GlobWrapper`]s.
+///
+/// Patterns are kept between parses, so deserializing the same config many times (for example, once per request in a server) only parses each glob once.
+///
+/// To keep user supplied globs from growing this forever, only the [`GLOB_CACHE_CAPACITY`] most recently used patterns are kept.
+static GLOB_CACHE: LazyLock<Mutex<BoundedCache<String, Pattern>>> = LazyLock::new(|| Mutex::new(BoundedCache::new(GLOB_CACHE_CAPACITY)));
+
+/// The maximum amount of patterns kept in [`GLOB_CACHE`].
+pub const GLOB_CACHE_CAPACITY: usize = 1024;
+
+/// Every glob parsed by [`cached_pattern`], to let tests check that globs aren't reparsed.
+#[cfg(test)]
+static PARSED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Gets the [`Pattern`] for `pattern` from [`GLOB_CACHE`], parsing and inserting it if needed.
+/// # Errors
+/// If the call to [`Pattern::new`] returns an error, that error is returned.
+fn cached_pattern(pattern: &str) -> Result<Pattern, PatternError> {
+    // The map is always left in a valid state, so poisoning doesn't matter.
+    let mut cache = GLOB_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(parsed) = cache.get(pattern) {
+        return Ok(parsed);
+    }
+    let parsed = Pattern::new(pattern)?;
+    #[cfg(test)]
+    PARSED.lock().unwrap_or_else(PoisonError::into_inner).push(pattern.to_string());
+    cache.insert(pattern.to_string(), parsed.clone());
+    Ok(parsed)
+}
 
 /// A wrapper around [`glob::Pattern`] and [`glob::MatchOptions`].
+///
+/// Parsed [`Pattern`]s are cached, so parsing or deserializing the same glob again reuses the already parsed [`Pattern`].
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Suitability)]
 #[serde(remote= "Self")]
 pub struct GlobWrapper {
@@ -42,7 +76,7 @@ impl FromStr for GlobWrapper {
 
     /// Simply treats the string as a glob and defaults the config.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Pattern::from_str(s).map(Into::into)
+        cached_pattern(s).map(Into::into)
     }
 }
 
@@ -72,7 +106,7 @@ struct SerdeMatchOptions {
 /// Deserializer to turn a string into a [`Pattern`].
 fn deserialize_pattern<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pattern, D::Error> {
     let pattern: String=Deserialize::deserialize(deserializer)?;
-    Pattern::new(&pattern).map_err(D::Error::custom)
+    cached_pattern(&pattern).map_err(D::Error::custom)
 }
 
 /// Serializer to turn a [`Pattern`] into a string.
@@ -94,3 +128,25 @@ impl GlobWrapper {
         self.pattern.matches_path_with(path, self.options)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializing_twice_parses_once() {
+        let pattern = "deserializing_twice_parses_once*";
+        let parse_count = || PARSED.lock().unwrap_or_else(PoisonError::into_inner).iter().filter(|parsed| *parsed == pattern).count();
+
+        let a: GlobWrapper = serde_json::from_str(&format!("{pattern:?}")).unwrap();
+        assert!(a.matches("deserializing_twice_parses_once_a"));
+        assert_eq!(parse_count(), 1);
+
+        drop(a);
+
+        let b: GlobWrapper = serde_json::from_str(&format!("{pattern:?}")).unwrap();
+        assert!(b.matches("deserializing_twice_parses_once_b"));
+        assert_eq!(parse_count(), 1);
+    }
+}
@@ -0,0 +1,50 @@
+//! Glue to make hashing strings with [`sha2`], [`sha1`], and [`md5`] easier.
+//!
+//! Enabled by the `hash` feature flag.
+
+use std::fmt::Write;
+
+use serde::{Serialize, Deserialize};
+use sha2::Digest;
+
+use crate::types::*;
+use crate::util::*;
+
+/// The hash algorithms [`StringSource::Hash`] can use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Suitability)]
+pub enum HashAlgorithm {
+    /// SHA-256.
+    #[default]
+    Sha256,
+    /// SHA-1.
+    Sha1,
+    /// MD5.
+    Md5
+}
+
+impl HashAlgorithm {
+    /// Hashes `value` and returns the digest as lowercase hex.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::glue::*;
+    /// assert_eq!(HashAlgorithm::Sha256.hash("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    /// assert_eq!(HashAlgorithm::Sha1  .hash("abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    /// assert_eq!(HashAlgorithm::Md5   .hash("abc"), "900150983cd24fb0d6963f7d28e17f72");
+    /// ```
+    pub fn hash(&self, value: &str) -> String {
+        match self {
+            Self::Sha256 => to_hex(&sha2::Sha256::digest(value)),
+            Self::Sha1   => to_hex(&sha1::Sha1::digest(value)),
+            Self::Md5    => to_hex(&md5::Md5::digest(value))
+        }
+    }
+}
+
+/// Formats `bytes` as lowercase hex.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut ret, byte| {
+        // Writing to a `String` can't fail.
+        let _ = write!(ret, "{byte:02x}");
+        ret
+    })
+}
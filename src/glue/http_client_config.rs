@@ -1,19 +1,29 @@
 //! Allows configuring HTTP clients.
 
 use serde::{Serialize, Deserialize};
+use thiserror::Error;
 #[cfg(feature = "http")]
-use reqwest::header::HeaderMap;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue};
 
 use crate::types::*;
 use crate::glue::*;
 use crate::util::*;
 
 /// Used by [`Params`] to detail how a [`reqwest::blocking::Client`] should be made.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Suitability)]
+///
+/// Decompressing response bodies requires the `http-compression` feature, which is enabled by default and enables reqwest's `gzip`, `deflate`, and `brotli` features.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Suitability)]
 pub struct HttpClientConfig {
     /// [`reqwest::blocking::ClientBuilder::default_headers`]. Defaults to an empty [`HeaderMap`].
     #[serde(default, skip_serializing_if = "is_default", with = "crate::glue::headermap")]
     pub default_headers: HeaderMap,
+    /// Headers whose values are computed from the job state when each request is sent, applied on top of [`Self::default_headers`]. Defaults to an empty [`Vec`].
+    ///
+    /// Because a client's headers can't depend on the job, these are not applied by [`Self::apply`] and are instead made by [`JobStateView::dynamic_headers`].
+    ///
+    /// As responses can depend on these headers and the cache is only keyed by URL, [`Mapper::ExpandRedirect`] and [`Mapper::ExpandShortener`] neither read nor write the cache when this is non-empty.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub dynamic_headers: Vec<DynamicHeader>,
     /// Roughly corresponds to [`reqwest::redirect::Policy`]. Defaults to [`RedirectPolicy::default`].
     #[serde(default, skip_serializing_if = "is_default")]
     pub redirect_policy: RedirectPolicy,
@@ -31,7 +41,130 @@ pub struct HttpClientConfig {
     pub referer: bool,
     /// [`reqwest::blocking::ClientBuilder::danger_accept_invalid_certs`]. Defaults to [`false`].
     #[serde(default, skip_serializing_if = "is_default")]
-    pub danger_accept_invalid_certs: bool
+    pub danger_accept_invalid_certs: bool,
+    /// [`reqwest::blocking::ClientBuilder::gzip`]. Defaults to [`true`].
+    #[cfg(feature = "http-compression")]
+    #[serde(default = "get_true", skip_serializing_if = "is_true")]
+    pub gzip: bool,
+    /// [`reqwest::blocking::ClientBuilder::deflate`]. Defaults to [`true`].
+    #[cfg(feature = "http-compression")]
+    #[serde(default = "get_true", skip_serializing_if = "is_true")]
+    pub deflate: bool,
+    /// [`reqwest::blocking::ClientBuilder::brotli`]. Defaults to [`true`].
+    #[cfg(feature = "http-compression")]
+    #[serde(default = "get_true", skip_serializing_if = "is_true")]
+    pub brotli: bool,
+    /// If [`Some`], requests to the same host sent by [`Mapper::ExpandRedirect`], [`Mapper::ExpandShortener`], and [`RequestConfig`]s are spaced out using [`JobStateView::rate_limiter`]. Defaults to [`None`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub rate_limit: Option<RateLimitConfig>
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            default_headers: Default::default(),
+            dynamic_headers: Default::default(),
+            redirect_policy: Default::default(),
+            https_only: false,
+            proxies: Default::default(),
+            no_proxy: false,
+            referer: false,
+            danger_accept_invalid_certs: false,
+            #[cfg(feature = "http-compression")]
+            gzip: true,
+            #[cfg(feature = "http-compression")]
+            deflate: true,
+            #[cfg(feature = "http-compression")]
+            brotli: true,
+            rate_limit: None
+        }
+    }
+}
+
+/// A header whose value is computed from the job state when a request is sent.
+///
+/// Used by [`HttpClientConfig::dynamic_headers`] and [`HttpClientConfigDiff::add_dynamic_headers`].
+/// # Examples
+/// ```
+/// # use std::io::{Read, Write};
+/// # use url_cleaner::types::*;
+/// # use url_cleaner::glue::*;
+/// url_cleaner::job_state!(job_state; params = Params {
+///     vars: [("referer".into(), "https://example.com/from".into())].into(),
+///     ..Default::default()
+/// };);
+///
+/// // Responds with the request it got.
+/// let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+/// let url = format!("http://{}/", listener.local_addr().unwrap());
+/// std::thread::spawn(move || {
+///     let (mut stream, _) = listener.accept().unwrap();
+///     let mut request = [0u8; 4096];
+///     let len = stream.read(&mut request).unwrap();
+///     write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {len}\r\nConnection: close\r\n\r\n").unwrap();
+///     stream.write_all(&request[..len]).unwrap();
+/// });
+///
+/// let request = RequestConfig {
+///     url: url.into(),
+///     client_config_diff: Some(HttpClientConfigDiff {
+///         add_dynamic_headers: vec![
+///             DynamicHeader {name: "Referer".into(), value: StringSource::Var(Box::new("referer".into()))},
+///             DynamicHeader {name: "X-Missing".into(), value: StringSource::Var(Box::new("missing".into()))}
+///         ],
+///         ..Default::default()
+///     }),
+///     ..Default::default()
+/// };
+///
+/// let echoed = request.response(&job_state.to_view()).unwrap().to_lowercase();
+/// assert!(echoed.contains("referer: https://example.com/from\r\n"));
+/// assert!(!echoed.contains("x-missing"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Suitability)]
+pub struct DynamicHeader {
+    /// The name of the header.
+    pub name: String,
+    /// The value of the header.
+    ///
+    /// If the call to [`StringSource::get`] returns [`None`], the header is omitted.
+    pub value: StringSource
+}
+
+/// The enum of all possible errors [`DynamicHeader::make`] can return.
+#[derive(Debug, Error)]
+pub enum MakeDynamicHeaderError {
+    /// Returned when a [`StringSourceError`] is encountered.
+    #[error(transparent)]
+    StringSourceError(#[from] Box<StringSourceError>),
+    /// Returned when an [`InvalidHeaderName`] is encountered.
+    #[error(transparent)]
+    InvalidHeaderName(#[from] InvalidHeaderName),
+    /// Returned when an [`InvalidHeaderValue`] is encountered.
+    #[error(transparent)]
+    InvalidHeaderValue(#[from] InvalidHeaderValue)
+}
+
+impl From<StringSourceError> for MakeDynamicHeaderError {
+    fn from(value: StringSourceError) -> Self {
+        Self::StringSourceError(Box::new(value))
+    }
+}
+
+impl DynamicHeader {
+    /// Makes the header's name and value, or [`None`] if [`Self::value`] is [`None`].
+    /// # Errors
+    /// If the call to [`StringSource::get`] returns an error, that error is returned.
+    ///
+    /// If [`Self::name`] isn't a valid [`HeaderName`], returns the error [`MakeDynamicHeaderError::InvalidHeaderName`].
+    ///
+    /// If the value isn't a valid [`HeaderValue`], returns the error [`MakeDynamicHeaderError::InvalidHeaderValue`].
+    pub fn make(&self, job_state: &JobStateView) -> Result<Option<(HeaderName, HeaderValue)>, MakeDynamicHeaderError> {
+        Ok(match self.value.get(job_state)? {
+            Some(value) => Some((HeaderName::from_bytes(self.name.as_bytes())?, HeaderValue::from_str(&value)?)),
+            None => None
+        })
+    }
 }
 
 /// Bandaid fix until [`reqwest::redirect::Policy`] stops sucking.
@@ -73,8 +206,48 @@ impl HttpClientConfig {
             temp = temp.proxy(proxy.clone().make()?);
         }
         if self.no_proxy {temp = temp.no_proxy();}
+        #[cfg(feature = "http-compression")]
+        {temp = temp.gzip(self.gzip).deflate(self.deflate).brotli(self.brotli);}
         Ok(temp)
     }
+
+    /// Makes a [`reqwest::blocking::Client`] using [`Self::apply`].
+    /// # Errors
+    /// If the call to [`Self::apply`] returns an error, that error is returned.
+    ///
+    /// If the call to [`reqwest::blocking::ClientBuilder::build`] returns an error, that error is returned.
+    pub fn client(&self) -> reqwest::Result<reqwest::blocking::Client> {
+        self.apply(reqwest::blocking::ClientBuilder::new())?.build()
+    }
+
+    /// [`Self::apply`] but for the async [`reqwest::ClientBuilder`].
+    /// # Errors
+    /// If the call to [`ProxyConfig::make`] returns an error, that error is returned.
+    #[cfg(feature = "async")]
+    pub fn apply_async(&self, client: reqwest::ClientBuilder) -> reqwest::Result<reqwest::ClientBuilder> {
+        let mut temp = client.default_headers(self.default_headers.clone())
+            .redirect(self.redirect_policy.clone().into())
+            .https_only(self.https_only)
+            .referer(self.referer)
+            .danger_accept_invalid_certs(self.danger_accept_invalid_certs);
+        for proxy in &self.proxies {
+            temp = temp.proxy(proxy.clone().make()?);
+        }
+        if self.no_proxy {temp = temp.no_proxy();}
+        #[cfg(feature = "http-compression")]
+        {temp = temp.gzip(self.gzip).deflate(self.deflate).brotli(self.brotli);}
+        Ok(temp)
+    }
+
+    /// [`Self::client`] but makes an async [`reqwest::Client`] using [`Self::apply_async`].
+    /// # Errors
+    /// If the call to [`Self::apply_async`] returns an error, that error is returned.
+    ///
+    /// If the call to [`reqwest::ClientBuilder::build`] returns an error, that error is returned.
+    #[cfg(feature = "async")]
+    pub fn async_client(&self) -> reqwest::Result<reqwest::Client> {
+        self.apply_async(reqwest::ClientBuilder::new())?.build()
+    }
 }
 
 /// Allows changing [`HttpClientConfig`].
@@ -86,6 +259,9 @@ pub struct HttpClientConfigDiff {
     /// Appends headers to [`HttpClientConfig::default_headers`]. Defaults to an empty [`HeaderMap`].
     #[serde(default, skip_serializing_if = "is_default", with = "crate::glue::headermap")]
     pub add_default_headers: HeaderMap,
+    /// Appends headers to [`HttpClientConfig::dynamic_headers`]. Defaults to an empty [`Vec`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub add_dynamic_headers: Vec<DynamicHeader>,
     /// If [`Some`], overwrites [`HttpClientConfig::https_only`]. Defaults to [`None`].
     #[serde(default, skip_serializing_if = "is_default")]
     pub https_only: Option<bool>,
@@ -103,27 +279,93 @@ pub struct HttpClientConfigDiff {
     pub referer: Option<bool>,
     /// IF [`Some`], overwrites [`HttpClientConfig::danger_accept_invalid_certs`]. Defaults to [`None`].
     #[serde(default, skip_serializing_if = "is_default")]
-    pub danger_accept_invalid_certs: Option<bool>
+    pub danger_accept_invalid_certs: Option<bool>,
+    /// If [`Some`], overwrites [`HttpClientConfig::gzip`]. Defaults to [`None`].
+    #[cfg(feature = "http-compression")]
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub gzip: Option<bool>,
+    /// If [`Some`], overwrites [`HttpClientConfig::deflate`]. Defaults to [`None`].
+    #[cfg(feature = "http-compression")]
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub deflate: Option<bool>,
+    /// If [`Some`], overwrites [`HttpClientConfig::brotli`]. Defaults to [`None`].
+    #[cfg(feature = "http-compression")]
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub brotli: Option<bool>,
+    /// If [`Some`], overwrites [`HttpClientConfig::rate_limit`]. Defaults to [`None`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub rate_limit: Option<RateLimitConfig>
 }
 
 impl HttpClientConfigDiff {
     /// Applies the differences specified in `self` to `to` in the following order:
     /// 1. If [`Self::redirect_policy`] is [`Some`], overwrite `to`'s [`HttpClientConfig::redirect_policy`].
     /// 2. Append [`Self::add_default_headers`] to `to`'s [`HttpClientConfig::default_headers`].
-    /// 3. If [`Self::https_only`] is [`Some`], overwrite `to`'s [`HttpClientConfig::https_only`].
-    /// 4. If [`Self::set_proxies`] is [`Some`], overwrite `to`'s [`HttpClientConfig::proxies`].
-    /// 5. Append [`Self::add_proxies`] to `to`'s [`HttpClientConfig::proxies`].
-    /// 6. If [`Self::no_proxy`] is [`Some`], overwrite `to`'s [`HttpClientConfig::no_proxy`].
-    /// 7. If [`Self::referer`] is [`Some`], overwrite `to`'s [`HttpClientConfig::referer`].
-    /// 8. If [`Self::danger_accept_invalid_certs`] is [`Some`], overwrite `to`'s [`HttpClientConfig::danger_accept_invalid_certs`].
+    /// 3. Append [`Self::add_dynamic_headers`] to `to`'s [`HttpClientConfig::dynamic_headers`].
+    /// 4. If [`Self::https_only`] is [`Some`], overwrite `to`'s [`HttpClientConfig::https_only`].
+    /// 5. If [`Self::set_proxies`] is [`Some`], overwrite `to`'s [`HttpClientConfig::proxies`].
+    /// 6. Append [`Self::add_proxies`] to `to`'s [`HttpClientConfig::proxies`].
+    /// 7. If [`Self::no_proxy`] is [`Some`], overwrite `to`'s [`HttpClientConfig::no_proxy`].
+    /// 8. If [`Self::referer`] is [`Some`], overwrite `to`'s [`HttpClientConfig::referer`].
+    /// 9. If [`Self::danger_accept_invalid_certs`] is [`Some`], overwrite `to`'s [`HttpClientConfig::danger_accept_invalid_certs`].
+    /// 10. If [`Self::gzip`], [`Self::deflate`], and/or [`Self::brotli`] are [`Some`], overwrite the corresponding fields of `to`.
+    /// 11. If [`Self::rate_limit`] is [`Some`], overwrite `to`'s [`HttpClientConfig::rate_limit`].
     pub fn apply(&self, to: &mut HttpClientConfig) {
         if let Some(new_redirect_policy) = &self.redirect_policy {to.redirect_policy = new_redirect_policy.clone();}
         to.default_headers.extend(self.add_default_headers.clone());
+        to.dynamic_headers.extend(self.add_dynamic_headers.iter().cloned());
         if let Some(https_only) = self.https_only {to.https_only = https_only;}
         if let Some(set_proxies) = &self.set_proxies {to.proxies.clone_from(set_proxies);}
         to.proxies.extend(self.add_proxies.clone());
         if let Some(no_proxy) = self.no_proxy {to.no_proxy = no_proxy;}
         if let Some(referer) = self.referer {to.no_proxy = referer;}
         if let Some(danger_accept_invalid_certs) = self.danger_accept_invalid_certs {to.danger_accept_invalid_certs = danger_accept_invalid_certs;}
+        #[cfg(feature = "http-compression")]
+        {
+            if let Some(gzip   ) = self.gzip    {to.gzip    = gzip   ;}
+            if let Some(deflate) = self.deflate {to.deflate = deflate;}
+            if let Some(brotli ) = self.brotli  {to.brotli  = brotli ;}
+        }
+        if let Some(rate_limit) = self.rate_limit {to.rate_limit = Some(rate_limit);}
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
+#[cfg(all(test, feature = "http-compression"))]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    /// `Hello, gzip!` compressed with gzip.
+    const GZIPPED: [u8; 32] = [0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xf3, 0x48, 0xcd, 0xc9, 0xc9, 0xd7, 0x51, 0x48, 0xaf, 0xca, 0x2c, 0x50, 0x04, 0x00, 0x3e, 0x3d, 0x0f, 0x10, 0x0c, 0x00, 0x00, 0x00];
+
+    /// Serves [`GZIPPED`] with `Content-Encoding: gzip` once and returns the URL to request.
+    fn serve_gzipped_once() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request).unwrap();
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", GZIPPED.len()).unwrap();
+            stream.write_all(&GZIPPED).unwrap();
+        });
+        url
+    }
+
+    #[test]
+    fn gzip_response_is_decompressed() {
+        let url = serve_gzipped_once();
+        let client = HttpClientConfig::default().apply(reqwest::blocking::ClientBuilder::new()).unwrap().build().unwrap();
+        assert_eq!(client.get(url).send().unwrap().text().unwrap(), "Hello, gzip!");
+    }
+
+    #[test]
+    fn gzip_can_be_disabled() {
+        let url = serve_gzipped_once();
+        let mut config = HttpClientConfig::default();
+        HttpClientConfigDiff {gzip: Some(false), ..Default::default()}.apply(&mut config);
+        let client = config.apply(reqwest::blocking::ClientBuilder::new()).unwrap().build().unwrap();
+        assert_eq!(client.get(url).send().unwrap().bytes().unwrap().as_ref(), GZIPPED);
     }
 }
@@ -0,0 +1,146 @@
+//! Provides [`RateLimiter`] and [`RateLimitConfig`] which space out HTTP requests to the same host.
+//!
+//! Enabled by the `http` feature flag.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Serialize, Deserialize};
+
+use crate::types::*;
+use crate::util::*;
+
+/// How many requests per second can be sent to each host.
+///
+/// Used by `HttpClientConfig::rate_limit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Suitability)]
+pub struct RateLimitConfig {
+    /// The amount of requests that can be sent to a host per second.
+    ///
+    /// If zero, requests aren't limited.
+    pub requests_per_second: u32,
+    /// The amount of requests that can be sent to a host at once before being limited to [`Self::requests_per_second`].
+    ///
+    /// Defaults to `1`. Zero is treated as `1`.
+    #[serde(default = "get_one", skip_serializing_if = "is_one")]
+    pub burst: u32
+}
+
+/// Serde helper function.
+const fn get_one() -> u32 {1}
+/// Serde helper function.
+#[allow(clippy::trivially_copy_pass_by_ref, reason = "Serde requires this signature.")]
+const fn is_one(x: &u32) -> bool {*x == 1}
+
+/// The tokens left for a host.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    /// The amount of tokens left.
+    tokens: f64,
+    /// When [`Self::tokens`] was last refilled.
+    last_refill: Instant
+}
+
+/// A token bucket per host, shared between every [`Job`] made from the same [`JobsConfig`].
+///
+/// Cloning a [`Self`] gives a handle to the same buckets.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter(Arc<Mutex<HashMap<String, Bucket>>>);
+
+impl RateLimiter {
+    /// Blocks the current thread until a request to `host` is allowed by `config`, then consumes a token.
+    ///
+    /// If [`RateLimitConfig::requests_per_second`] is zero, returns immediately.
+    /// # Examples
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use url_cleaner::glue::*;
+    /// let limiter = RateLimiter::default();
+    /// let config = RateLimitConfig {requests_per_second: 10, burst: 1};
+    ///
+    /// let start = Instant::now();
+    /// limiter.acquire("example.com", &config);
+    /// limiter.acquire("example.org", &config);
+    /// assert!(start.elapsed() < Duration::from_millis(50));
+    ///
+    /// limiter.acquire("example.com", &config);
+    /// assert!(start.elapsed() >= Duration::from_millis(90));
+    /// ```
+    #[allow(dead_code, reason = "Public API.")]
+    pub fn acquire(&self, host: &str, config: &RateLimitConfig) {
+        while let Some(wait) = self.try_acquire(host, config) {
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// [`Self::acquire`] but waits with [`tokio::time::sleep`] instead of blocking the current thread.
+    #[cfg(feature = "async")]
+    pub async fn acquire_async(&self, host: &str, config: &RateLimitConfig) {
+        while let Some(wait) = self.try_acquire(host, config) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// If a request to `host` is allowed by `config`, consumes a token and returns [`None`].
+    ///
+    /// Otherwise returns how long to wait before trying again.
+    fn try_acquire(&self, host: &str, config: &RateLimitConfig) -> Option<Duration> {
+        if config.requests_per_second == 0 {return None;}
+        let rate = f64::from(config.requests_per_second);
+        let burst = f64::from(config.burst.max(1));
+        let mut buckets = self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let now = Instant::now();
+        let bucket = buckets.entry(host.to_string()).or_insert(Bucket {tokens: burst, last_refill: now});
+        bucket.tokens = (bucket.tokens + now.duration_since(bucket.last_refill).as_secs_f64() * rate).min(burst);
+        bucket.last_refill = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_host_is_spaced_out_and_other_hosts_arent() {
+        let limiter = RateLimiter::default();
+        let config = RateLimitConfig {requests_per_second: 20, burst: 1};
+
+        let start = Instant::now();
+        for _ in 0..3 {limiter.acquire("example.com", &config);}
+        assert!(start.elapsed() >= Duration::from_millis(90));
+
+        let start = Instant::now();
+        limiter.acquire("example.org", &config);
+        limiter.acquire("example.net", &config);
+        assert!(start.elapsed() < Duration::from_millis(40));
+    }
+
+    #[test]
+    fn shared_between_clones_and_threads() {
+        let limiter = RateLimiter::default();
+        let config = RateLimitConfig {requests_per_second: 20, burst: 2};
+
+        let start = Instant::now();
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                let limiter = limiter.clone();
+                s.spawn(move || limiter.acquire("example.com", &config));
+            }
+        });
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[test]
+    fn deserialize_default_burst() {
+        let config: RateLimitConfig = serde_json::from_str(r#"{"requests_per_second": 2}"#).unwrap();
+        assert_eq!(config, RateLimitConfig {requests_per_second: 2, burst: 1});
+    }
+}
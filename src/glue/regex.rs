@@ -3,35 +3,59 @@
 //! Enabled by the `regex` feature flag.
 
 use std::str::FromStr;
-use std::sync::OnceLock;
+use std::sync::{Arc, Mutex, OnceLock, LazyLock, PoisonError};
 
 use serde::{Serialize, Deserialize};
 use regex::Regex;
 
 use crate::types::*;
 use crate::util::*;
+use super::bounded_cache::BoundedCache;
 
 mod regex_parts;
 pub use regex_parts::*;
 
+/// The compiled [`Regex`]es shared between all [`RegexWrapper`]s with the same [`RegexParts`].
+///
+/// Regexes are kept even once no [`RegexWrapper`] uses them, so deserializing the same config many times (for example, once per request in a server) only compiles each regex once.
+///
+/// To keep user supplied patterns from growing this forever, only the [`REGEX_CACHE_CAPACITY`] most recently used regexes are kept.
+static REGEX_CACHE: LazyLock<Mutex<BoundedCache<RegexParts, Arc<Regex>>>> = LazyLock::new(|| Mutex::new(BoundedCache::new(REGEX_CACHE_CAPACITY)));
+
+/// The maximum amount of regexes kept in [`REGEX_CACHE`].
+pub const REGEX_CACHE_CAPACITY: usize = 1024;
+
+/// Every [`RegexParts`] compiled by [`cached`], to let tests check that regexes aren't recompiled.
+#[cfg(test)]
+static COMPILED: Mutex<Vec<RegexParts>> = Mutex::new(Vec::new());
+
 /// A wrapper around both a [`OnceLock`] of a [`Regex`] and a [`RegexParts`].
 /// 
 /// Both are included to allow both lazy compilation and turning a [`Self`] back into a [`RegexParts`].
+///
+/// Compiled [`Regex`]es are cached, so all [`Self`]s with the same [`RegexParts`] share one [`Regex`].
+///
+/// If the [`Regex`] for a [`RegexParts`] has already been compiled, creating a [`Self`] (including by deserializing one) reuses it.
 /// Unfortunately, as they need to always be the same value, the fields of this struct are private.
 #[derive(Clone, Debug, Serialize, Deserialize, Suitability)]
 #[serde(from = "RegexParts", into = "RegexParts")]
 pub struct RegexWrapper {
     /// Allows the [`Regex`] to only be constructed when needed.
     #[suitable(always)]
-    regex: OnceLock<Regex>,
+    regex: OnceLock<Arc<Regex>>,
     /// Instructions for how to create the [`Regex`] to put in [`Self::regex`].
     parts: RegexParts
 }
 
 impl From<RegexParts> for RegexWrapper {
+    /// If [`REGEX_CACHE`] already has the [`Regex`] for `parts`, it's used. Otherwise the [`Regex`] is compiled when first needed.
     fn from(parts: RegexParts) -> Self {
+        let regex = OnceLock::new();
+        if let Some(cached) = REGEX_CACHE.lock().unwrap_or_else(PoisonError::into_inner).get(&parts) {
+            let _ = regex.set(cached);
+        }
         Self {
-            regex: OnceLock::new(),
+            regex,
             parts
         }
     }
@@ -76,7 +100,7 @@ impl TryFrom<RegexWrapper> for Regex {
 
     fn try_from(value: RegexWrapper) -> Result<Self, Self::Error> {
         if let Some(regex) = value.regex.into_inner() {
-            Ok(regex)
+            Ok(Arc::unwrap_or_clone(regex))
         } else {
             value.parts.build()
         }
@@ -85,16 +109,71 @@ impl TryFrom<RegexWrapper> for Regex {
 
 impl RegexWrapper {
     /// Gets the cached compiled regex or compiles it first if it's not already cached.
+    ///
+    /// If any [`Self`] with the same [`RegexParts`] has compiled its regex and it's still in the cache, that regex is reused instead of compiling it again.
     /// # Errors
     /// Although regexes are ensured to be syntactically valid when a [`Self`] is created, it is possible for actually compiling a regex to result in a DFA bigger than the default limit in the [`regex`] crate which causes an error.
     /// 
     /// For details, please see the regex crate's documentation on [untrusted patterns](https://docs.rs/regex/latest/regex/index.html#untrusted-patterns) for details.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::glue::*;
+    /// let a = RegexWrapper::from("a+b");
+    /// let b = RegexWrapper::from("a+b");
+    /// let c = RegexWrapper::from("a+c");
+    ///
+    /// assert!( std::ptr::eq(a.get_regex().unwrap(), b.get_regex().unwrap()));
+    /// assert!(!std::ptr::eq(a.get_regex().unwrap(), c.get_regex().unwrap()));
+    ///
+    /// // Still reused after every other user is dropped.
+    /// let a_ptr: *const ::regex::Regex = a.get_regex().unwrap();
+    /// drop((a, b));
+    /// let d: RegexWrapper = serde_json::from_str(r#""a+b""#).unwrap();
+    /// assert!(std::ptr::eq(d.get_regex().unwrap(), a_ptr));
+    /// ```
     pub fn get_regex(&self) -> Result<&Regex, regex::Error> {
         if let Some(regex) = self.regex.get() {
             Ok(regex)
         } else {
-            let temp = self.parts.build()?;
+            let temp = cached(&self.parts)?;
             Ok(self.regex.get_or_init(|| temp))
         }
     }
 }
+
+/// Gets the [`Regex`] for `parts` from [`REGEX_CACHE`], compiling and inserting it if needed.
+/// # Errors
+/// If the call to [`RegexParts::build`] returns an error, that error is returned.
+fn cached(parts: &RegexParts) -> Result<Arc<Regex>, regex::Error> {
+    // The map is always left in a valid state, so poisoning doesn't matter.
+    let mut cache = REGEX_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(regex) = cache.get(parts) {
+        return Ok(regex);
+    }
+    let regex = Arc::new(parts.build()?);
+    #[cfg(test)]
+    COMPILED.lock().unwrap_or_else(PoisonError::into_inner).push(parts.clone());
+    cache.insert(parts.clone(), regex.clone());
+    Ok(regex)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializing_twice_compiles_once() {
+        let pattern = "^deserializing_twice_compiles_once$";
+        let compile_count = || COMPILED.lock().unwrap_or_else(PoisonError::into_inner).iter().filter(|parts| parts.pattern == pattern).count();
+
+        let a: RegexWrapper = serde_json::from_str(&format!("{pattern:?}")).unwrap();
+        assert!(a.get_regex().unwrap().is_match("deserializing_twice_compiles_once"));
+        assert_eq!(compile_count(), 1);
+        drop(a);
+
+        let b: RegexWrapper = serde_json::from_str(&format!("{pattern:?}")).unwrap();
+        assert!(b.get_regex().unwrap().is_match("deserializing_twice_compiles_once"));
+        assert_eq!(compile_count(), 1);
+    }
+}
@@ -17,7 +17,7 @@ use crate::util::*;
 /// Contains the rules for constructing a [`Regex`].
 /// 
 /// The pattern is guaranteed to be valid.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(remote = "Self")]
 pub struct RegexParts {
     /// The pattern passed into [`RegexBuilder::new`].
@@ -114,7 +114,7 @@ impl TryFrom<RegexParts> for Regex {
 }
 
 /// The configuration determining how a regular expression works.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Suitability)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Suitability)]
 pub struct RegexConfig {
     /// The value passed into [`RegexBuilder::case_insensitive`]. Defaults to `false`. This flags character is `'i'`.
     #[serde(default               , skip_serializing_if = "is_false")] pub case_insensitive: bool,
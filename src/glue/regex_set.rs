@@ -0,0 +1,175 @@
+//! Provides [`RegexSetWrapper`], a lazy, serializable/deserializable, and deconstructable wrapper around [`RegexSet`].
+//!
+//! Enabled by the `regex` feature flag.
+
+use std::sync::OnceLock;
+
+use serde::{Serialize, Deserialize};
+use regex::{RegexSet, RegexSetBuilder};
+use super::RegexConfig;
+#[expect(unused_imports, reason = "Used in a doc comment.")]
+use super::RegexWrapper;
+
+use crate::types::*;
+use crate::util::*;
+
+/// Contains the rules for constructing a [`RegexSet`].
+///
+/// Can be deserialized from either a list of patterns or a map with a `patterns` field and the fields of [`RegexConfig`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(from = "RegexSetPartsRepr")]
+pub struct RegexSetParts {
+    /// The patterns passed into [`RegexSetBuilder::new`].
+    pub patterns: Vec<String>,
+    /// The configuration flags applied to every pattern.
+    #[serde(flatten)]
+    pub config: RegexConfig
+}
+
+/// Helper enum to allow deserializing [`RegexSetParts`] from just a list of patterns.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RegexSetPartsRepr {
+    /// Just the patterns, using the default [`RegexConfig`].
+    Patterns(Vec<String>),
+    /// The patterns and the config.
+    Full {
+        /// [`RegexSetParts::patterns`].
+        patterns: Vec<String>,
+        /// [`RegexSetParts::config`].
+        #[serde(flatten)]
+        config: RegexConfig
+    }
+}
+
+impl From<RegexSetPartsRepr> for RegexSetParts {
+    fn from(value: RegexSetPartsRepr) -> Self {
+        match value {
+            RegexSetPartsRepr::Patterns(patterns) => Self {patterns, config: Default::default()},
+            RegexSetPartsRepr::Full {patterns, config} => Self {patterns, config}
+        }
+    }
+}
+
+impl<T: Into<String>> FromIterator<T> for RegexSetParts {
+    /// Uses the default [`RegexConfig`].
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            patterns: iter.into_iter().map(Into::into).collect(),
+            config: Default::default()
+        }
+    }
+}
+
+impl RegexSetParts {
+    /// Creates the regex set.
+    /// # Errors
+    /// If the call to [`RegexSetBuilder::build`] returns an error, that error is returned.
+    pub fn build(&self) -> Result<RegexSet, regex::Error> {
+        debug!(RegexSetParts::build, self);
+        RegexSetBuilder::new(&self.patterns)
+            .case_insensitive(self.config.case_insensitive)
+            .crlf(self.config.crlf)
+            .dot_matches_new_line(self.config.dot_matches_new_line)
+            .ignore_whitespace(self.config.ignore_whitespace)
+            .line_terminator(self.config.line_terminator)
+            .multi_line(self.config.multi_line)
+            .octal(self.config.octal)
+            .swap_greed(self.config.swap_greed)
+            .unicode(self.config.unicode)
+            .build()
+    }
+}
+
+impl Suitability for RegexSetParts {
+    fn assert_suitability(&self, config: &Config) {
+        self.build().unwrap_or_else(|_| panic!("RegexSet to be buildable: {self:?}"));
+        self.config.assert_suitability(config);
+    }
+}
+
+/// A wrapper around both a [`OnceLock`] of a [`RegexSet`] and a [`RegexSetParts`].
+///
+/// Like [`RegexWrapper`] but for testing many patterns in one pass.
+///
+/// Unlike [`RegexWrapper`], compiled [`RegexSet`]s are not interned.
+#[derive(Clone, Debug, Serialize, Deserialize, Suitability)]
+#[serde(from = "RegexSetParts", into = "RegexSetParts")]
+pub struct RegexSetWrapper {
+    /// Allows the [`RegexSet`] to only be constructed when needed.
+    #[suitable(always)]
+    regex_set: OnceLock<RegexSet>,
+    /// Instructions for how to create the [`RegexSet`] to put in [`Self::regex_set`].
+    parts: RegexSetParts
+}
+
+impl From<RegexSetParts> for RegexSetWrapper {
+    fn from(parts: RegexSetParts) -> Self {
+        Self {
+            regex_set: OnceLock::new(),
+            parts
+        }
+    }
+}
+
+impl<T: Into<String>> FromIterator<T> for RegexSetWrapper {
+    /// Uses the default [`RegexConfig`].
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        RegexSetParts::from_iter(iter).into()
+    }
+}
+
+impl PartialEq for RegexSetWrapper {
+    /// Simply calls [`RegexSetParts::eq`].
+    fn eq(&self, other: &Self) -> bool {
+        self.parts.eq(&other.parts)
+    }
+}
+impl Eq for RegexSetWrapper {}
+
+impl From<RegexSetWrapper> for RegexSetParts {
+    fn from(value: RegexSetWrapper) -> Self {
+        value.parts
+    }
+}
+
+impl AsRef<RegexSetParts> for RegexSetWrapper {
+    fn as_ref(&self) -> &RegexSetParts {
+        &self.parts
+    }
+}
+
+impl RegexSetWrapper {
+    /// Gets the cached compiled regex set or compiles it first if it's not already cached.
+    /// # Errors
+    /// If the call to [`RegexSetParts::build`] returns an error, that error is returned.
+    pub fn get_regex_set(&self) -> Result<&RegexSet, regex::Error> {
+        if let Some(regex_set) = self.regex_set.get() {
+            Ok(regex_set)
+        } else {
+            let temp = self.parts.build()?;
+            Ok(self.regex_set.get_or_init(|| temp))
+        }
+    }
+
+    /// Returns `true` if any of the patterns match `haystack`.
+    /// # Errors
+    /// If the call to [`Self::get_regex_set`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::glue::*;
+    /// let set = RegexSetWrapper::from_iter(["^a+$", "^b+$"]);
+    ///
+    /// assert!( set.is_match("aaa").unwrap());
+    /// assert!( set.is_match("bb" ).unwrap());
+    /// assert!(!set.is_match("ab" ).unwrap());
+    ///
+    /// let set2: RegexSetWrapper = serde_json::from_str(r#"["^a+$", "^b+$"]"#).unwrap();
+    /// assert_eq!(set, set2);
+    /// let set3: RegexSetWrapper = serde_json::from_str(r#"{"patterns": ["^A+$"], "case_insensitive": true}"#).unwrap();
+    /// assert!(set3.is_match("aaa").unwrap());
+    /// ```
+    pub fn is_match(&self, haystack: &str) -> Result<bool, regex::Error> {
+        Ok(self.get_regex_set()?.is_match(haystack))
+    }
+}
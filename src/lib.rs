@@ -16,6 +16,7 @@
 #![cfg_attr(feature = "cache", doc = "    cache_path: Default::default(),")]
 //!     params: Default::default(),
 //!     commons: Default::default(),
+//!     canonicalize_output: None,
 //!     rules: Rules(vec![
 //!         Rule::Normal {
 //!             condition: Condition::Always,
@@ -30,7 +31,8 @@
 #![cfg_attr(feature = "cache", doc = "        // Doesn't do anything expensive until actually used.")]
 #![cfg_attr(feature = "cache", doc = "        // You should use a global static `OnceLock` if you have to make multiple `Jobs`s with the same `Cache`.")]
 #![cfg_attr(feature = "cache", doc = "        // That's fine because cloning a `Cache` is extremely cheap, because it's an `Arc<Mutex<InnerCache>>`.")]
-#![cfg_attr(feature = "cache", doc = "        cache: config.cache_path.as_str().into()")]
+#![cfg_attr(feature = "cache", doc = "        cache: config.cache_path.as_str().into(),")]
+#![cfg_attr(feature = "http", doc = "        rate_limiter: Default::default(),")]
 //!     },
 //!     context: Default::default(),
 //!     // Ideally you'll be handling URLs in bulk.
@@ -48,5 +50,13 @@ pub mod glue;
 pub mod types;
 pub mod testing;
 pub(crate) mod util;
+#[cfg(all(feature = "wasm", target_family = "wasm"))]
+pub mod wasm;
+
+#[cfg(all(target_family = "wasm", any(feature = "http", feature = "commands", feature = "cache")))]
+compile_error!("The `http`, `commands`, and `cache` features don't work on WebAssembly. Build with `--no-default-features` and only enable the features you need.");
+
+#[cfg(all(target_family = "wasm", target_os = "unknown", feature = "time"))]
+compile_error!("The `time` feature doesn't work on `wasm32-unknown-unknown` because `std::time::SystemTime::now` panics there.");
 
 pub use types::{Config, Jobs, JobConfig};
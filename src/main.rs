@@ -1,12 +1,18 @@
 //! URL Cleaner originally started as a project to remove tracking garbage from URLs but has since grown into a very powerful URL manipulation tool.
 
 use std::path::PathBuf;
-use std::io::{self, IsTerminal};
+use std::io::{self, IsTerminal, Write};
 use std::borrow::Cow;
 use std::process::ExitCode;
 use std::str::FromStr;
+use std::collections::HashSet;
+#[cfg(feature = "default-config")]
+use std::ffi::OsString;
 
-use clap::{Parser, CommandFactory};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use clap::{Parser, CommandFactory, FromArgMatches, ValueEnum, ArgMatches};
 use thiserror::Error;
 
 mod glue;
@@ -28,8 +34,14 @@ mod util;
 #[cfg_attr(feature = "regex"              , doc = "regex"         )]
 #[cfg_attr(feature = "glob"               , doc = "glob"          )]
 #[cfg_attr(feature = "http"               , doc = "http"          )]
+#[cfg_attr(feature = "http-compression"   , doc = "http-compression")]
+#[cfg_attr(feature = "async"              , doc = "async"         )]
 #[cfg_attr(feature = "cache"              , doc = "cache"         )]
 #[cfg_attr(feature = "base64"             , doc = "base64"        )]
+#[cfg_attr(feature = "hash"               , doc = "hash"          )]
+#[cfg_attr(feature = "unicode"            , doc = "unicode"       )]
+#[cfg_attr(feature = "time"               , doc = "time"          )]
+#[cfg_attr(feature = "fs"                 , doc = "fs"            )]
 #[cfg_attr(feature = "commands"           , doc = "commands"      )]
 #[cfg_attr(feature = "custom"             , doc = "custom"        )]
 #[cfg_attr(feature = "debug"              , doc = "debug"         )]
@@ -39,22 +51,32 @@ mod util;
 #[cfg_attr(not(feature = "regex"         ), doc = "regex"         )]
 #[cfg_attr(not(feature = "glob"          ), doc = "glob"          )]
 #[cfg_attr(not(feature = "http"          ), doc = "http"          )]
+#[cfg_attr(not(feature = "http-compression"), doc = "http-compression")]
+#[cfg_attr(not(feature = "async"         ), doc = "async"         )]
 #[cfg_attr(not(feature = "cache"         ), doc = "cache"         )]
 #[cfg_attr(not(feature = "base64"        ), doc = "base64"        )]
+#[cfg_attr(not(feature = "hash"          ), doc = "hash"          )]
+#[cfg_attr(not(feature = "unicode"       ), doc = "unicode"       )]
+#[cfg_attr(not(feature = "time"          ), doc = "time"          )]
+#[cfg_attr(not(feature = "fs"            ), doc = "fs"            )]
 #[cfg_attr(not(feature = "commands"      ), doc = "commands"      )]
 #[cfg_attr(not(feature = "custom"        ), doc = "custom"        )]
 #[cfg_attr(not(feature = "debug"         ), doc = "debug"         )]
 pub struct Args {
     /// The URLs to clean before the URLs in the STDIN.
     pub urls: Vec<String>,
-    /// The JSON config to use. If unspecified and URL Cleaner was compiled with the default-config feature, use the default config compiled into URL Cleaner.
+    /// The JSON config to use. If unspecified and URL Cleaner was compiled with the default-config feature, use the config at the path in the URL_CLEANER_CONFIG environment variable, or, if that's unset, the default config compiled into URL Cleaner.
+    ///
+    /// Can be specified multiple times to layer configs, in order, using Config::merge.
     #[cfg(feature = "default-config")]
     #[arg(short      , long)]
-    pub config: Option<PathBuf>,
+    pub config: Vec<PathBuf>,
     /// The JSON config to use. Has to be set because this instance of URL Cleaner was compiled without a default config.
+    ///
+    /// Can be specified multiple times to layer configs, in order, using Config::merge.
     #[cfg(not(feature = "default-config"))]
-    #[arg(short      , long)]
-    pub config: PathBuf,
+    #[arg(short      , long, required = true)]
+    pub config: Vec<PathBuf>,
     /// Overrides the config's [`Config::cache_path`].
     #[cfg(feature = "cache")]
     #[arg(             long)]
@@ -62,9 +84,37 @@ pub struct Args {
     /// Output JSON. It is intended to be identical to URL Cleaner Site's output, so while some of the output is "redundant", it's important.
     #[arg(short      , long)]
     pub json: bool,
-    /// Additional ParamsDiffs to apply before the rest of the options.
+    /// Print the original URL alongside each cleaned URL.
+    /// In JSON mode, also says whether the URL changed and which query parameters were removed.
+    #[arg(             long, verbatim_doc_comment)]
+    pub show_diff: bool,
+    /// For each URL, print which rules matched and how they changed the URL to STDERR.
+    /// Each URL's trace is a `Trace` line with the original URL followed by one JSON TraceEvent per line.
+    #[arg(             long, verbatim_doc_comment)]
+    pub trace: bool,
+    /// Skip STDIN lines that aren't valid UTF-8 instead of treating them as errors.
+    /// Skipped lines print an empty line so the output stays aligned with the input, or nothing in JSON mode.
+    /// Skipped lines don't print an error or affect the exit code.
+    #[arg(             long, verbatim_doc_comment)]
+    pub skip_invalid_input: bool,
+    /// Time every Condition and Mapper and, once all URLs are done, print a table of each variant's total time, call count, and mean time to STDERR.
+    /// Times include the time of Conditions and Mappers inside them.
+    #[arg(             long, verbatim_doc_comment)]
+    pub profile: bool,
+    /// If an input URL doesn't have a scheme, like `example.com/x` or `//example.com/x`, add this scheme.
+    #[arg(             long, value_names = ["SCHEME"])]
+    pub assume_scheme: Option<String>,
+    /// If no URLs are cleaned or fail to be cleaned, exit with code 3 instead of 0.
     #[arg(             long)]
+    pub error_on_empty: bool,
+    /// Additional ParamsDiffs to apply before the rest of the options.
+    /// Applied in the order they're specified, interleaved with `--params-diff-json`.
+    #[arg(             long, verbatim_doc_comment)]
     pub params_diff: Vec<PathBuf>,
+    /// Additional ParamsDiffs, as inline JSON, to apply before the rest of the options.
+    /// Applied in the order they're specified, interleaved with `--params-diff`.
+    #[arg(             long, verbatim_doc_comment)]
+    pub params_diff_json: Vec<String>,
     /// Stuff to make a [`ParamsDiff`] from the CLI.
     #[command(flatten)]
     pub params_diff_args: ParamsDiffArgParser,
@@ -95,11 +145,31 @@ pub struct Args {
     /// Exact behavior is unspecified, but generally restricts noisy and insecure stuff like Debug variants and commands.
     #[arg(             long, verbatim_doc_comment)]
     pub test_suitability: bool,
+    /// Statically checks the config for obviously dead or redundant rules and prints any warnings.
+    /// If any warnings are found, exits with code 1.
+    #[arg(             long, verbatim_doc_comment)]
+    pub lint: bool,
     /// Amount of threads to process jobs in.
     /// 
     /// Zero gets the current CPU threads.
     #[arg(long, default_value_t = 0)]
     pub threads: usize,
+    /// Periodically print how many URLs were processed, how many succeeded and failed, and how fast, to STDERR.
+    ///
+    /// `--progress` and `--progress auto` only print progress if STDERR is a terminal. `--progress always` always prints progress.
+    #[arg(long, num_args = 0..=1, default_missing_value = "auto", value_name = "WHEN")]
+    pub progress: Option<ProgressWhen>,
+    /// With `--progress`, also print progress every this many URLs instead of only every second.
+    ///
+    /// Zero only prints progress every second.
+    #[arg(long, default_value_t = 0)]
+    pub progress_every: usize,
+    /// The maximum amount of HTTP requests to have in flight at once.
+    ///
+    /// Zero uses the default of 64.
+    #[cfg(feature = "async")]
+    #[arg(long, default_value_t = 0)]
+    pub max_in_flight: usize,
     /// When enabled, only prints timing info.
     ///
     /// Produces more reliable timing info for some reason.
@@ -108,15 +178,52 @@ pub struct Args {
     pub debug_just_print_times: bool
 }
 
+/// When to print progress for `--progress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProgressWhen {
+    /// Only print progress if STDERR is a terminal.
+    Auto,
+    /// Always print progress.
+    Always
+}
+
+/// Counts of URLs processed for `--progress`.
+#[derive(Debug, Default)]
+struct Progress {
+    /// The amount of URLs that were cleaned successfully.
+    ok: AtomicUsize,
+    /// The amount of URLs that failed to be cleaned.
+    err: AtomicUsize,
+    /// Set when all URLs are processed.
+    done: AtomicBool
+}
+
+impl Progress {
+    /// Prints the current progress to STDERR, overwriting the previous line.
+    fn print(&self, start: Instant) {
+        let ok  = self.ok .load(Ordering::Relaxed);
+        let err = self.err.load(Ordering::Relaxed);
+        #[allow(clippy::arithmetic_side_effects, reason = "Can't process usize::MAX URLs.")]
+        let processed = ok + err;
+        #[allow(clippy::cast_precision_loss, reason = "Only used for display.")]
+        let rate = processed as f64 / start.elapsed().as_secs_f64().max(f64::EPSILON);
+        eprint!("\r\x1b[KProcessed: {processed} (ok: {ok}, err: {err}), {rate:.1} URLs/sec");
+    }
+}
+
 /// The enum of all errors that can occur when using the URL Cleaner CLI tool.
 #[derive(Debug, Error)]
 pub enum CliError {
     /// Returned when a [`GetConfigError`] is encountered.
     #[error(transparent)] GetConfigError(#[from] GetConfigError),
+    /// Returned when a [`MergeConfigError`] is encountered.
+    #[error(transparent)] MergeConfigError(#[from] MergeConfigError),
     /// Returned when URL Cleaner fails to load a [`ParamsDiff`] file.
     #[error(transparent)] CantLoadParamsDiffFile(std::io::Error),
     /// Returned when URL Cleaner fails to parse a [`ParamsDiff`] file's contents.
     #[error(transparent)] CantParseParamsDiffFile(serde_json::Error),
+    /// Returned when URL Cleaner fails to parse a `--params-diff-json` [`ParamsDiff`].
+    #[error(transparent)] CantParseParamsDiffJson(serde_json::Error),
     /// Returned when URL Cleaner fails to parse a [`JobsContext`].
     #[error(transparent)] CantParseJobsContext(serde_json::Error),
     /// Returned when a [`SerdeJsonError`] is encountered.
@@ -124,7 +231,10 @@ pub enum CliError {
     /// Returned when trying to load a [`Tests`] file fails.
     #[error(transparent)] CantLoadTests(io::Error),
     /// Returned when trying to parse a [`Tests`] file fails.
-    #[error(transparent)] CantParseTests(serde_json::Error)
+    #[error(transparent)] CantParseTests(serde_json::Error),
+    /// Returned when making the [`AsyncHttpRunner`]'s runtime fails.
+    #[cfg(feature = "async")]
+    #[error(transparent)] CantMakeAsyncRuntime(io::Error)
 }
 
 /// Shorthand for serializing a string to JSON.
@@ -132,11 +242,75 @@ fn str_to_json_str(s: &str) -> String {
     serde_json::to_string(s).expect("Serializing a string to never fail.")
 }
 
+/// Serializes an error to the `{"message": ..., "code": ..., "variant": ...}` object used by `--json`.
+fn error_to_json_str<E: std::error::Error + ErrorCode>(e: &E) -> String where for<'a> &'a E: Into<&'static str> {
+    format!("{{\"message\":{},\"code\":{},\"variant\":{}}}", str_to_json_str(&e.to_string()), str_to_json_str(e.code()), str_to_json_str(&format!("{e:?}")))
+}
+
+/// Gets the names of the query parameters in `original` that aren't in `cleaned`, in the order they first appear in `original`.
+fn removed_query_params(original: &url::Url, cleaned: &url::Url) -> Vec<String> {
+    let cleaned_names = cleaned.query_pairs().map(|(name, _)| name).collect::<HashSet<_>>();
+    let mut ret = Vec::new();
+    for (name, _) in original.query_pairs() {
+        if !cleaned_names.contains(&name) && !ret.iter().any(|x: &String| **x == *name) {
+            ret.push(name.into_owned());
+        }
+    }
+    ret
+}
+
+/// Where to get a [`ParamsDiff`] from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParamsDiffSource<'a> {
+    /// From `--params-diff`.
+    File(&'a PathBuf),
+    /// From `--params-diff-json`.
+    Json(&'a str)
+}
+
+impl ParamsDiffSource<'_> {
+    /// Loads the [`ParamsDiff`].
+    /// # Errors
+    /// If the call to [`std::fs::read_to_string`] returns an error, returns the error [`CliError::CantLoadParamsDiffFile`].
+    ///
+    /// If the call to [`serde_json::from_str`] returns an error, returns the error [`CliError::CantParseParamsDiffFile`] or [`CliError::CantParseParamsDiffJson`].
+    fn load(self) -> Result<ParamsDiff, CliError> {
+        match self {
+            Self::File(path) => serde_json::from_str(&std::fs::read_to_string(path).map_err(CliError::CantLoadParamsDiffFile)?).map_err(CliError::CantParseParamsDiffFile),
+            Self::Json(json) => serde_json::from_str(json).map_err(CliError::CantParseParamsDiffJson)
+        }
+    }
+}
+
+/// Gets the `--params-diff` and `--params-diff-json` [`ParamsDiffSource`]s in the order they were specified.
+fn ordered_params_diff_sources<'a>(matches: &ArgMatches, args: &'a Args) -> Vec<ParamsDiffSource<'a>> {
+    let mut ret = matches.indices_of("params_diff").into_iter().flatten().zip(args.params_diff.iter().map(ParamsDiffSource::File))
+        .chain(matches.indices_of("params_diff_json").into_iter().flatten().zip(args.params_diff_json.iter().map(String::as_str).map(ParamsDiffSource::Json)))
+        .collect::<Vec<_>>();
+    ret.sort_by_key(|(index, _)| *index);
+    ret.into_iter().map(|(_, source)| source).collect()
+}
+
+/// The environment variable checked for a config path when `--config` isn't specified.
+#[cfg(feature = "default-config")]
+const CONFIG_ENV_VAR: &str = "URL_CLEANER_CONFIG";
+
+/// Gets the path of the first config to load.
+///
+/// `--config` takes precedence over [`CONFIG_ENV_VAR`], which takes precedence over the default config, represented by [`None`].
+///
+/// An empty [`CONFIG_ENV_VAR`] is treated as unset.
+#[cfg(feature = "default-config")]
+fn first_config_path(arg: Option<&PathBuf>, env_var: Option<OsString>) -> Option<PathBuf> {
+    arg.cloned().or_else(|| env_var.filter(|path| !path.is_empty()).map(PathBuf::from))
+}
+
 fn main() -> Result<ExitCode, CliError> {
     let some_ok  = std::sync::Mutex::new(false);
     let some_err = std::sync::Mutex::new(false);
 
-    let args = Args::parse();
+    let matches = Args::command().get_matches();
+    let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     #[cfg(feature = "debug")]
     util::DEBUG_JUST_PRINT_TIMES.set(args.debug_just_print_times).expect("No poisoning.");
@@ -144,14 +318,18 @@ fn main() -> Result<ExitCode, CliError> {
     let print_args = args.print_args;
     if print_args {println!("{args:?}");}
 
+    let mut config_paths = args.config.iter();
     #[cfg(feature = "default-config")]
-    let mut config = Config::get_default_no_cache_or_load(args.config.as_deref())?;
+    let mut config = Config::get_default_no_cache_or_load(first_config_path(config_paths.next(), std::env::var_os(CONFIG_ENV_VAR)))?;
     #[cfg(not(feature = "default-config"))]
-    let mut config = Config::load_from_file(&args.config)?;
+    let mut config = Config::load_from_file(config_paths.next().expect("Clap to require at least one config."))?;
+    for path in config_paths {
+        config.merge(Config::load_from_file(path)?)?;
+    }
 
-    let mut params_diffs: Vec<ParamsDiff> = args.params_diff
+    let mut params_diffs: Vec<ParamsDiff> = ordered_params_diff_sources(&matches, &args)
         .into_iter()
-        .map(|path| serde_json::from_str(&std::fs::read_to_string(path).map_err(CliError::CantLoadParamsDiffFile)?).map_err(CliError::CantParseParamsDiffFile))
+        .map(ParamsDiffSource::load)
         .collect::<Result<Vec<_>, _>>()?;
     if args.params_diff_args.does_anything() {
         match args.params_diff_args.try_into() {
@@ -170,13 +348,19 @@ fn main() -> Result<ExitCode, CliError> {
     }
 
     let json = args.json;
+    let show_diff = args.show_diff;
+    let trace = args.trace;
+    let skip_invalid_input = args.skip_invalid_input;
+    let error_on_empty = args.error_on_empty;
+    let assume_scheme = args.assume_scheme.as_deref();
 
     let print_params     = args.print_params;
     let print_config     = args.print_config;
     let tests            = args.tests;
     let test_suitability = args.test_suitability;
+    let lint             = args.lint;
 
-    let no_cleaning = print_args || print_params_diffs || print_params || print_config || test_suitability || tests.is_some();
+    let no_cleaning = print_args || print_params_diffs || print_params || print_config || test_suitability || lint || tests.is_some();
 
     if print_params {println!("{}", serde_json::to_string(&config.params)?);}
     if print_config {println!("{}", serde_json::to_string(&config)?);}
@@ -187,25 +371,49 @@ fn main() -> Result<ExitCode, CliError> {
         }
         println!("\nAll tests passed!");
     }
+    if lint {
+        let warnings: Vec<LintWarning> = config.lint();
+        for warning in &warnings {
+            println!("{warning}");
+        }
+        if !warnings.is_empty() {return Ok(ExitCode::FAILURE);}
+    }
 
     if no_cleaning {std::process::exit(0);}
 
+    #[cfg(feature = "async")]
+    if args.max_in_flight != 0 {
+        AsyncHttpRunner::set_global(AsyncHttpRunner::new(args.max_in_flight).map_err(CliError::CantMakeAsyncRuntime)?).expect("The global AsyncHttpRunner to not have been used yet.");
+    }
+
+    let progress = match args.progress {
+        Some(ProgressWhen::Auto) => io::stderr().is_terminal(),
+        Some(ProgressWhen::Always) => true,
+        None => false
+    };
+    let progress_every = args.progress_every;
+    let progress_counts = Progress::default();
+    let progress_counts_ref = &progress_counts;
+
     let mut threads = args.threads;
     if threads == 0 {threads = std::thread::available_parallelism().expect("To be able to get the available parallelism.").into();}
     let (in_senders , in_recievers ) = (0..threads).map(|_| std::sync::mpsc::channel::<Result<String, io::Error>>()).collect::<(Vec<_>, Vec<_>)>();
-    let (out_senders, out_recievers) = (0..threads).map(|_| std::sync::mpsc::channel::<Result<Result<url::Url, DoJobError>, MakeJobError>>()).collect::<(Vec<_>, Vec<_>)>();
+    let (out_senders, out_recievers) = (0..threads).map(|_| std::sync::mpsc::channel::<Option<Result<(Option<url::Url>, Result<url::Url, DoJobError>), MakeJobError>>>()).collect::<(Vec<_>, Vec<_>)>();
 
     let jobs_config = JobsConfig {
         #[cfg(feature = "cache")]
         cache: args.cache_path.as_ref().unwrap_or(&config.cache_path).clone().into(),
-        config: Cow::Owned(config)
+        config: Cow::Owned(config),
+        #[cfg(feature = "http")]
+        rate_limiter: Default::default()
     };
     let jobs_config_ref = &jobs_config;
-    let jobs_context = if let Some(jobs_context_string) = args.jobs_context {
+    let mut jobs_context: JobsContext = if let Some(jobs_context_string) = args.jobs_context {
         serde_json::from_str(&jobs_context_string).map_err(CliError::CantParseJobsContext)?
     } else {
         Default::default()
     };
+    if args.profile {jobs_context.profile = Some(Default::default());}
     let jobs_context_ref = &jobs_context;
 
     std::thread::scope(|s| {
@@ -228,15 +436,42 @@ fn main() -> Result<ExitCode, CliError> {
         in_recievers.into_iter().zip(out_senders).enumerate().map(|(i, (ir, os))| {
             std::thread::Builder::new().name(format!("Worker {i}")).spawn_scoped(s, move || {
                 while let Ok(maybe_job_config_string) = ir.recv() {
+                    if skip_invalid_input && is_invalid_input(&maybe_job_config_string) {
+                        os.send(None).expect("The receiver to still exist.");
+                        continue;
+                    }
+
                     let ret = match maybe_job_config_string {
-                        Ok(job_config_string) => match JobConfig::from_str(&job_config_string) {
-                            Ok(job_config) => Ok(jobs_config_ref.new_job(job_config, jobs_context_ref).r#do()),
+                        Ok(job_config_string) => match assume_scheme.map_or_else(|| JobConfig::from_str(&job_config_string), |scheme| JobConfig::from_str_with_default_scheme(&job_config_string, scheme)) {
+                            Ok(job_config) => {
+                                let original = (show_diff || trace).then(|| job_config.url.url().clone());
+                                let job = jobs_config_ref.new_job(job_config, jobs_context_ref);
+                                let ret = if trace {
+                                    let (ret, events) = job.do_traced();
+                                    let mut stderr = io::stderr().lock();
+                                    let _ = writeln!(stderr, "Trace\t{}", original.as_ref().map(|original| original.as_str()).unwrap_or_default());
+                                    for event in events {
+                                        let _ = writeln!(stderr, "{}", serde_json::to_string(&event).expect("Serializing a TraceEvent to never fail."));
+                                    }
+                                    ret
+                                } else {
+                                    job.r#do()
+                                };
+                                Ok((original.filter(|_| show_diff), ret))
+                            },
                             Err(e) => Err(MakeJobError::MakeJobConfigError(e))
                         },
                         Err(e) => Err(MakeJobError::MakeJobConfigError(MakeJobConfigError::IoError(e)))
                     };
 
-                    os.send(ret).expect("The receiver to still exist.");
+                    if progress {
+                        match ret {
+                            Ok((_, Ok(_))) => progress_counts_ref.ok .fetch_add(1, Ordering::Relaxed),
+                            _              => progress_counts_ref.err.fetch_add(1, Ordering::Relaxed)
+                        };
+                    }
+
+                    os.send(Some(ret)).expect("The receiver to still exist.");
                 }
             }).expect("Making threads to work fine.");
         }).for_each(drop);
@@ -244,34 +479,66 @@ fn main() -> Result<ExitCode, CliError> {
         let some_ok_ref  = &some_ok;
         let some_err_ref = &some_err;
 
+        if progress {
+            std::thread::Builder::new().name("Progress".to_string()).spawn_scoped(s, move || {
+                let start = Instant::now();
+                let mut last_time = start;
+                let mut last_count = 0usize;
+                while !progress_counts_ref.done.load(Ordering::Acquire) {
+                    std::thread::sleep(Duration::from_millis(50));
+                    let count = progress_counts_ref.ok.load(Ordering::Relaxed).saturating_add(progress_counts_ref.err.load(Ordering::Relaxed));
+                    if last_time.elapsed() >= Duration::from_secs(1) || (progress_every != 0 && count.saturating_sub(last_count) >= progress_every) {
+                        progress_counts_ref.print(start);
+                        last_time = Instant::now();
+                        last_count = count;
+                    }
+                }
+                progress_counts_ref.print(start);
+                eprintln!();
+            }).expect("Making threads to work fine.");
+        }
+
         std::thread::Builder::new().name("Stdout".to_string()).spawn_scoped(s, move || {
             let mut disconnected = 0usize;
             let mut some_ok_ref_lock  = some_ok_ref .lock().expect("No panics.");
             let mut some_err_ref_lock = some_err_ref.lock().expect("No panics.");
 
             if json {
-                let mut first_job = true;
+                let mut count = 0usize;
 
                 print!("{{\"Ok\":{{\"urls\":[");
                 for or in out_recievers.iter().cycle() {
                     match or.recv() {
-                        Ok(Ok(Ok(url))) => {
-                            if !first_job {print!(",");}
+                        Ok(None) => {},
+                        Ok(Some(Ok((None, Ok(url))))) => {
+                            if count != 0 {print!(",");}
                             print!("{{\"Ok\":{{\"Ok\":{}}}}}", str_to_json_str(url.as_str()));
                             *some_ok_ref_lock = true;
-                            first_job = false;
+                            count = count.saturating_add(1);
                         },
-                        Ok(Ok(Err(e))) => {
-                            if !first_job {print!(",");}
-                            print!("{{\"Ok\":{{\"Err\":{{\"message\":{},\"variant\":{}}}}}}}", str_to_json_str(&e.to_string()), str_to_json_str(&format!("{e:?}")));
+                        Ok(Some(Ok((Some(original), Ok(url))))) => {
+                            if count != 0 {print!(",");}
+                            print!(
+                                "{{\"Ok\":{{\"Ok\":{{\"original\":{},\"cleaned\":{},\"changed\":{},\"removed_query_params\":{}}}}}}}",
+                                str_to_json_str(original.as_str()),
+                                str_to_json_str(url.as_str()),
+                                original != url,
+                                serde_json::to_string(&removed_query_params(&original, &url)).expect("Serializing a list of strings to never fail.")
+                            );
+                            *some_ok_ref_lock = true;
+                            count = count.saturating_add(1);
+                        },
+                        Ok(Some(Ok((_, Err(e))))) => {
+                            if count != 0 {print!(",");}
+                            print!("{{\"Ok\":{{\"Err\":{}}}}}", error_to_json_str(&e));
                             *some_err_ref_lock = true;
-                            first_job = false;
+                            count = count.saturating_add(1);
                         },
-                        Ok(Err(e)) => {
-                            if !first_job {print!(",");}
-                            print!("{{\"Err\":{{\"message\":{},\"variant\":{}}}}}", str_to_json_str(&e.to_string()), str_to_json_str(&format!("{e:?}")));
+                        Ok(Some(Err(e))) => {
+                            if count != 0 {print!(",");}
+                            print!("{{\"Err\":{}}}", error_to_json_str(&e));
                             *some_err_ref_lock = true;
-                            first_job = false;
+                            count = count.saturating_add(1);
                         },
                         Err(_) => {
                             #[allow(clippy::arithmetic_side_effects, reason = "Can't even come close to usize::MAX threads and this is capped by thread count.")]
@@ -281,20 +548,25 @@ fn main() -> Result<ExitCode, CliError> {
                     }
                 }
 
-                print!("]}}}}");
+                print!("],\"count\":{count}}}}}");
             } else {
                 for or in out_recievers.iter().cycle() {
                     match or.recv() {
-                        Ok(Ok(Ok(url))) => {
+                        Ok(None) => println!(),
+                        Ok(Some(Ok((None, Ok(url))))) => {
                             println!("{}", url.as_str());
                             *some_ok_ref_lock = true;
                         },
-                        Ok(Ok(Err(e))) => {
+                        Ok(Some(Ok((Some(original), Ok(url))))) => {
+                            println!("{}\t{}", original.as_str(), url.as_str());
+                            *some_ok_ref_lock = true;
+                        },
+                        Ok(Some(Ok((_, Err(e))))) => {
                             println!();
                             eprintln!("DoJobError\t{e:?}");
                             *some_err_ref_lock = true;
                         }
-                        Ok(Err(e)) => {
+                        Ok(Some(Err(e))) => {
                             println!();
                             eprintln!("MakeJobError\t{e:?}");
                             *some_err_ref_lock = true;
@@ -307,13 +579,120 @@ fn main() -> Result<ExitCode, CliError> {
                     }
                 }
             }
+
+            progress_counts_ref.done.store(true, Ordering::Release);
         }).expect("Making threads to work fine.");
     });
 
-    return Ok(match (*some_ok.lock().expect("No panics."), *some_err.lock().expect("No panics.")) {
-        (false, false) => 0,
+    if let Some(profile) = &jobs_context.profile {eprint!("{profile}");}
+
+    return Ok(exit_code(*some_ok.lock().expect("No panics."), *some_err.lock().expect("No panics."), error_on_empty).into());
+}
+
+/// Gets the exit code from whether any URLs were cleaned, whether any failed, and `--error-on-empty`.
+const fn exit_code(some_ok: bool, some_err: bool, error_on_empty: bool) -> u8 {
+    match (some_ok, some_err) {
+        (false, false) => if error_on_empty {3} else {0},
         (false, true ) => 1,
         (true , false) => 0,
         (true , true ) => 2
-    }.into());
+    }
+}
+
+/// If `line` is an error from reading a line that isn't valid UTF-8, for `--skip-invalid-input`.
+fn is_invalid_input(line: &Result<String, io::Error>) -> bool {
+    matches!(line, Err(e) if e.kind() == io::ErrorKind::InvalidData)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "default-config")]
+    fn first_config_path_precedence() {
+        let arg = PathBuf::from("arg.json");
+        assert_eq!(first_config_path(Some(&arg), Some("env.json".into())), Some(arg.clone()));
+        assert_eq!(first_config_path(Some(&arg), None                    ), Some(arg));
+        assert_eq!(first_config_path(None      , Some("env.json".into())), Some("env.json".into()));
+        assert_eq!(first_config_path(None      , Some("".into())        ), None);
+        assert_eq!(first_config_path(None      , None                    ), None);
+    }
+
+    #[test]
+    fn params_diff_json() {
+        let args = ["url-cleaner", "--params-diff-json", r#"{"flags": ["a"]}"#, "--params-diff", "b.json", "--params-diff-json", r#"{"flags": ["c"]}"#];
+        let matches = Args::command().get_matches_from(args);
+        let args = Args::from_arg_matches(&matches).unwrap();
+        let sources = ordered_params_diff_sources(&matches, &args);
+        assert_eq!(sources, [
+            ParamsDiffSource::Json(r#"{"flags": ["a"]}"#),
+            ParamsDiffSource::File(&"b.json".into()),
+            ParamsDiffSource::Json(r#"{"flags": ["c"]}"#)
+        ]);
+
+        let mut params = Params::default();
+        sources.first().unwrap().load().unwrap().apply(&mut params);
+        assert!(params.flags.contains("a"));
+
+        assert!(matches!(ParamsDiffSource::Json("{").load(), Err(CliError::CantParseParamsDiffJson(_))));
+    }
+
+    #[test]
+    fn exit_code_on_empty() {
+        assert_eq!(exit_code(false, false, false), 0);
+        assert_eq!(exit_code(false, false, true ), 3);
+        assert_eq!(exit_code(true , false, true ), 0);
+        assert_eq!(exit_code(false, true , true ), 1);
+        assert_eq!(exit_code(true , true , true ), 2);
+    }
+
+    #[test]
+    fn invalid_input_is_skippable() {
+        use std::io::BufRead;
+
+        let lines = io::Cursor::new(b"https://example.com/a\n\xff\xfe\nhttps://example.com/b\n").lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(!is_invalid_input(lines.first().unwrap()));
+        assert!( is_invalid_input(lines.get(1).unwrap()));
+        assert!(!is_invalid_input(lines.get(2).unwrap()));
+        assert_eq!(lines.get(2).unwrap().as_ref().unwrap(), "https://example.com/b");
+    }
+
+    #[test]
+    fn error_json_has_code() {
+        let e = DoJobError::from(ApplyConfigError::from(RuleError::from(MapperError::ExplicitError)));
+        let json = serde_json::from_str::<serde_json::Value>(&error_to_json_str(&e)).unwrap();
+        assert_eq!(json.get("message").unwrap(), "Mapper::Error was used.");
+        assert_eq!(json.get("code").unwrap(), "MapperError::ExplicitError");
+
+        let e = MakeJobError::from(MakeJobConfigError::from(url::Url::parse("").unwrap_err()));
+        let json = serde_json::from_str::<serde_json::Value>(&error_to_json_str(&e)).unwrap();
+        assert_eq!(json.get("code").unwrap(), "MakeJobConfigError::UrlParseError");
+    }
+
+    #[test]
+    fn removed_query_params_diff() {
+        let removed = |original: &str, cleaned: &str| removed_query_params(&url::Url::parse(original).unwrap(), &url::Url::parse(cleaned).unwrap());
+
+        assert_eq!(removed("https://example.com?a=1&b=2&c=3", "https://example.com?b=2"), ["a", "c"]);
+        assert_eq!(removed("https://example.com?a=1&b=2"    , "https://example.com"    ), ["a", "b"]);
+
+        // Duplicate keys are listed once, and only if every occurrence was removed.
+        assert_eq!(removed("https://example.com?a=1&b=2&a=3&b=4", "https://example.com?b=2"    ), ["a"]);
+        assert_eq!(removed("https://example.com?a=1&a=2"        , "https://example.com?a=2"    ), Vec::<String>::new());
+
+        // Reordered params aren't removed.
+        assert_eq!(removed("https://example.com?c=3&a=1&b=2", "https://example.com?a=1&b=2&c=3"), Vec::<String>::new());
+        assert_eq!(removed("https://example.com?c=3&a=1&b=2", "https://example.com?b=2&c=3"    ), ["a"]);
+
+        // No query in the original.
+        assert_eq!(removed("https://example.com" , "https://example.com?a=1"), Vec::<String>::new());
+        assert_eq!(removed("https://example.com?", "https://example.com"    ), Vec::<String>::new());
+        assert_eq!(removed("https://example.com" , "https://example.com"    ), Vec::<String>::new());
+
+        // Names are compared decoded.
+        assert_eq!(removed("https://example.com?a%20b=1&c=2", "https://example.com?a+b=1"), ["c"]);
+    }
 }
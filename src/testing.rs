@@ -58,7 +58,9 @@ impl TestSet {
             jobs_config: JobsConfig {
                 config,
                 #[cfg(feature = "cache")]
-                cache: Default::default()
+                cache: Default::default(),
+                #[cfg(feature = "http")]
+                rate_limiter: Default::default()
             },
             context: Cow::Borrowed(&self.jobs_context),
             job_configs_source: Box::new(job_configs.into_iter().map(Ok))
@@ -26,5 +26,13 @@ pub mod map;
 pub use map::*;
 pub mod error_behavior;
 pub use error_behavior::*;
+pub mod ord_cmp;
+pub use ord_cmp::*;
+pub mod host_details_field;
+pub use host_details_field::*;
+pub mod profile;
+pub use profile::*;
+pub mod error_code;
+pub use error_code::*;
 
 use crate::util::*;
@@ -7,11 +7,16 @@ use std::ops::Index;
 use serde::{Serialize, Deserialize};
 use url::{Url, UrlQuery, PathSegmentsMut, ParseError};
 use form_urlencoded::Serializer;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use thiserror::Error;
 
 use crate::types::*;
+use crate::util::*;
 
 mod host_details;
 pub use host_details::*;
+mod normalize_options;
+pub use normalize_options::*;
 
 /// A wrapper around [`Url`] that allows for some faster operations.
 ///
@@ -199,6 +204,207 @@ impl BetterUrl {
         })
     }
 
+    /// Appends a path segment, percent encoding it.
+    ///
+    /// If the path ends with a slash, the new segment replaces the empty last segment.
+    /// # Errors
+    /// If the URL is cannot-be-a-base, returns the error [`PathSegmentError::UrlDoesNotHaveAPath`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// let mut url = BetterUrl::parse("https://example.com").unwrap();
+    /// url.push_path_segment("a").unwrap();
+    /// assert_eq!(url.path(), "/a");
+    /// url.push_path_segment("b/c d").unwrap();
+    /// assert_eq!(url.path(), "/a/b%2Fc%20d");
+    ///
+    /// let mut url = BetterUrl::parse("https://example.com/a/").unwrap();
+    /// url.push_path_segment("b").unwrap();
+    /// assert_eq!(url.path(), "/a/b");
+    ///
+    /// BetterUrl::parse("mailto:a@example.com").unwrap().push_path_segment("a").unwrap_err();
+    /// ```
+    pub fn push_path_segment(&mut self, segment: &str) -> Result<(), PathSegmentError> {
+        self.url.path_segments_mut().map_err(|()| PathSegmentError::UrlDoesNotHaveAPath)?.pop_if_empty().push(segment);
+        Ok(())
+    }
+
+    /// Removes the last path segment and returns it, still percent encoded.
+    ///
+    /// If the URL is cannot-be-a-base or the path is just `/`, returns [`None`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// let mut url = BetterUrl::parse("https://example.com/a/b%20c").unwrap();
+    /// assert_eq!(url.pop_path_segment(), Some("b%20c".into()));
+    /// assert_eq!(url.path(), "/a");
+    /// assert_eq!(url.pop_path_segment(), Some("a".into()));
+    /// assert_eq!(url.path(), "/");
+    /// assert_eq!(url.pop_path_segment(), None);
+    /// assert_eq!(url.path(), "/");
+    ///
+    /// let mut url = BetterUrl::parse("https://example.com/a/").unwrap();
+    /// assert_eq!(url.pop_path_segment(), Some("".into()));
+    /// assert_eq!(url.path(), "/a");
+    ///
+    /// assert_eq!(BetterUrl::parse("mailto:a@example.com").unwrap().pop_path_segment(), None);
+    /// ```
+    pub fn pop_path_segment(&mut self) -> Option<String> {
+        if self.url.path() == "/" {return None;}
+        let ret = self.url.path_segments()?.next_back()?.to_string();
+        self.url.path_segments_mut().ok()?.pop();
+        Some(ret)
+    }
+
+    /// Inserts a path segment at `index`, percent encoding it.
+    ///
+    /// Like Python's `list.insert`, an `index` equal to the amount of segments appends and negative indices insert before the segment that many from the end.
+    ///
+    /// Unlike [`Self::push_path_segment`], a trailing empty segment is treated like any other segment.
+    /// # Errors
+    /// If the URL is cannot-be-a-base, returns the error [`PathSegmentError::UrlDoesNotHaveAPath`].
+    ///
+    /// If `index` is out of bounds, returns the error [`PathSegmentError::SegmentNotFound`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// let mut url = BetterUrl::parse("https://example.com/a/c").unwrap();
+    /// url.insert_path_segment(1, "b").unwrap();
+    /// assert_eq!(url.path(), "/a/b/c");
+    /// url.insert_path_segment(0, "/").unwrap();
+    /// assert_eq!(url.path(), "/%2F/a/b/c");
+    /// url.insert_path_segment(4, "d").unwrap();
+    /// assert_eq!(url.path(), "/%2F/a/b/c/d");
+    /// url.insert_path_segment(-1, "x").unwrap();
+    /// assert_eq!(url.path(), "/%2F/a/b/c/x/d");
+    ///
+    /// url.insert_path_segment( 7, "y").unwrap_err();
+    /// url.insert_path_segment(-7, "y").unwrap_err();
+    /// BetterUrl::parse("mailto:a@example.com").unwrap().insert_path_segment(0, "a").unwrap_err();
+    /// ```
+    pub fn insert_path_segment(&mut self, index: isize, segment: &str) -> Result<(), PathSegmentError> {
+        let mut segments = self.url.path_segments().ok_or(PathSegmentError::UrlDoesNotHaveAPath)?.collect::<Vec<_>>();
+        let encoded = utf8_percent_encode(segment, PATH_SEGMENT_ASCII_SET).to_string();
+        segments.insert(neg_range_boundary(index, segments.len()).ok_or(PathSegmentError::SegmentNotFound)?, &encoded);
+        let new_path = format!("/{}", segments.join("/"));
+        self.url.set_path(&new_path);
+        Ok(())
+    }
+
+    /// Removes the path segment at `index` and returns it, still percent encoded.
+    ///
+    /// Negative indices count from the end.
+    /// # Errors
+    /// If the URL is cannot-be-a-base, returns the error [`PathSegmentError::UrlDoesNotHaveAPath`].
+    ///
+    /// If `index` is out of bounds, returns the error [`PathSegmentError::SegmentNotFound`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// let mut url = BetterUrl::parse("https://example.com/a/b%20c/d/e").unwrap();
+    /// assert_eq!(url.remove_path_segment(1).unwrap(), "b%20c");
+    /// assert_eq!(url.path(), "/a/d/e");
+    /// assert_eq!(url.remove_path_segment(-1).unwrap(), "e");
+    /// assert_eq!(url.path(), "/a/d");
+    /// assert_eq!(url.remove_path_segment(-2).unwrap(), "a");
+    /// assert_eq!(url.path(), "/d");
+    ///
+    /// url.remove_path_segment( 1).unwrap_err();
+    /// url.remove_path_segment(-2).unwrap_err();
+    ///
+    /// assert_eq!(url.remove_path_segment(0).unwrap(), "d");
+    /// assert_eq!(url.path(), "/");
+    ///
+    /// BetterUrl::parse("mailto:a@example.com").unwrap().remove_path_segment(0).unwrap_err();
+    /// ```
+    pub fn remove_path_segment(&mut self, index: isize) -> Result<String, PathSegmentError> {
+        let mut segments = self.url.path_segments().ok_or(PathSegmentError::UrlDoesNotHaveAPath)?.collect::<Vec<_>>();
+        let ret = segments.remove(neg_index(index, segments.len()).ok_or(PathSegmentError::SegmentNotFound)?).to_string();
+        let new_path = format!("/{}", segments.join("/"));
+        self.url.set_path(&new_path);
+        Ok(ret)
+    }
+
+    /// If the URL has an explicit port that is the default port for its scheme according to [`default_port`].
+    ///
+    /// [`Url`] already removes explicit default ports for the schemes it knows, so this only matters for the other schemes in [`default_port`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// assert!(!BetterUrl::parse("https://example.com:443").unwrap().has_explicit_default_port());
+    /// assert!(!BetterUrl::parse("git://example.com"      ).unwrap().has_explicit_default_port());
+    /// assert!( BetterUrl::parse("git://example.com:9418" ).unwrap().has_explicit_default_port());
+    /// assert!(!BetterUrl::parse("git://example.com:443"  ).unwrap().has_explicit_default_port());
+    /// ```
+    pub fn has_explicit_default_port(&self) -> bool {
+        self.port().is_some() && self.port() == default_port(self.scheme())
+    }
+
+    /// If [`Self::has_explicit_default_port`], removes the port.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// let mut url = BetterUrl::parse("git://example.com:9418").unwrap();
+    /// url.remove_default_port();
+    /// assert_eq!(url.as_str(), "git://example.com");
+    ///
+    /// let mut url = BetterUrl::parse("git://example.com:443").unwrap();
+    /// url.remove_default_port();
+    /// assert_eq!(url.as_str(), "git://example.com:443");
+    /// ```
+    #[allow(clippy::missing_panics_doc, reason = "Shouldn't ever happen.")]
+    pub fn remove_default_port(&mut self) {
+        if self.has_explicit_default_port() {
+            self.set_port(None).expect("A URL with a port to be able to have its port removed.");
+        }
+    }
+
+    /// Normalizes the URL according to the provided [`NormalizeOptions`].
+    /// # Errors
+    /// If the call to [`Url::set_host`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// let mut url = BetterUrl::parse("git://EXAMPLE.com.:9418/a").unwrap();
+    /// url.normalize(NormalizeOptions::default()).unwrap();
+    /// assert_eq!(url.as_str(), "git://EXAMPLE.com.:9418/a");
+    ///
+    /// let mut url = BetterUrl::parse("git://EXAMPLE.com.:9418/a").unwrap();
+    /// url.normalize(NormalizeOptions {remove_default_port: true, ..Default::default()}).unwrap();
+    /// assert_eq!(url.as_str(), "git://EXAMPLE.com./a");
+    ///
+    /// let mut url = BetterUrl::parse("git://EXAMPLE.com.:9418/a").unwrap();
+    /// url.normalize(NormalizeOptions {lowercase_host: true, ..Default::default()}).unwrap();
+    /// assert_eq!(url.as_str(), "git://example.com.:9418/a");
+    ///
+    /// let mut url = BetterUrl::parse("git://EXAMPLE.com.:9418/a").unwrap();
+    /// url.normalize(NormalizeOptions {remove_fqdn_period: true, ..Default::default()}).unwrap();
+    /// assert_eq!(url.as_str(), "git://EXAMPLE.com:9418/a");
+    ///
+    /// let mut url = BetterUrl::parse("git://EXAMPLE.com.:9418/a").unwrap();
+    /// url.normalize(NormalizeOptions {remove_default_port: true, lowercase_host: true, remove_fqdn_period: true}).unwrap();
+    /// assert_eq!(url.as_str(), "git://example.com/a");
+    ///
+    /// let mut url = BetterUrl::parse("https://example.com.:8443/a").unwrap();
+    /// url.normalize(NormalizeOptions {remove_default_port: true, lowercase_host: true, remove_fqdn_period: true}).unwrap();
+    /// assert_eq!(url.as_str(), "https://example.com:8443/a");
+    /// ```
+    #[allow(clippy::missing_panics_doc, reason = "Shouldn't ever happen.")]
+    pub fn normalize(&mut self, options: NormalizeOptions) -> Result<(), ParseError> {
+        if options.remove_default_port {
+            self.remove_default_port();
+        }
+        if options.lowercase_host && self.host_str().is_some_and(|host| host.chars().any(char::is_uppercase)) {
+            let new_host = self.host_str().expect("The URL to have a host.").to_lowercase();
+            self.set_host(Some(&new_host))?;
+        }
+        if options.remove_fqdn_period && matches!(self.host_details(), Some(HostDetails::Domain(domain_details)) if domain_details.is_fqdn()) {
+            let new_host = self.host_str().expect(HDA).strip_suffix('.').expect("FQDNs to end with a period.").to_string();
+            self.set_host(Some(&new_host))?;
+        }
+        Ok(())
+    }
+
     /// Gets the inner [`Url`].
     pub fn url(&self) -> &Url{
         &self.url
@@ -239,6 +445,20 @@ impl BetterUrl {
     }
 }
 
+/// The characters [`Url`] percent encodes in path segments.
+pub(crate) const PATH_SEGMENT_ASCII_SET: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b'#').add(b'<').add(b'>').add(b'?').add(b'`').add(b'{').add(b'}').add(b'/').add(b'%');
+
+/// The enum of errors [`BetterUrl::push_path_segment`], [`BetterUrl::insert_path_segment`], and [`BetterUrl::remove_path_segment`] can return.
+#[derive(Debug, Error)]
+pub enum PathSegmentError {
+    /// Urls that are [cannot-be-a-base](https://docs.rs/url/latest/url/struct.Url.html#method.cannot_be_a_base) don't have a path.
+    #[error("Urls that are cannot-be-a-base don't have a path.")]
+    UrlDoesNotHaveAPath,
+    /// Returned when the requested segment is not found.
+    #[error("The requested segment was not found.")]
+    SegmentNotFound
+}
+
 impl PartialEq<BetterUrl> for BetterUrl {
     fn eq(&self, other: &BetterUrl) -> bool {
         self.url == other.url
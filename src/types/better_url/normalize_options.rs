@@ -0,0 +1,47 @@
+//! Options for [`BetterUrl::normalize`].
+
+use serde::{Serialize, Deserialize};
+
+#[allow(unused_imports, reason = "Doc links.")]
+use url::Url;
+use crate::types::*;
+use crate::util::*;
+
+/// Options for [`BetterUrl::normalize`].
+///
+/// Each option defaults to [`false`].
+///
+/// There is no option for resolving `.` and `..` path segments because [`Url::parse`] always does that.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Suitability)]
+pub struct NormalizeOptions {
+    /// If the URL's port is the default port for its scheme, remove it.
+    ///
+    /// [`Url`] already does this for `http`, `https`, `ws`, `wss`, and `ftp` so this only matters for the other schemes in [`default_port`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub remove_default_port: bool,
+    /// Lowercase the URL's host.
+    ///
+    /// [`Url`] already does this for `http`, `https`, `ws`, `wss`, `ftp`, and `file` so this only matters for other schemes.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub lowercase_host: bool,
+    /// If the URL's host is a fully qualified domain name, remove the trailing `.`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub remove_fqdn_period: bool
+}
+
+/// Gets the default port of the specified scheme.
+///
+/// In addition to the schemes [`Url`] knows the default ports of, this also knows `gopher`, `ssh`, `git`, `irc`, and `ircs`.
+pub fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        "gopher" => Some(70),
+        "ssh" => Some(22),
+        "git" => Some(9418),
+        "irc" => Some(6667),
+        "ircs" => Some(6697),
+        _ => None
+    }
+}
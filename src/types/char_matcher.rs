@@ -4,6 +4,7 @@ use std::collections::HashSet;
 
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use strum::IntoStaticStr;
 
 use crate::types::*;
 
@@ -141,7 +142,8 @@ pub enum CharMatcher {
 }
 
 /// The enum of all possible errors [`CharMatcher::satisfied_by`] can return.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "CharMatcherError::")]
 pub enum CharMatcherError {
     /// Returned when [`CharMatcher::IsDigitRadix`] has a radix greater than 36 which would make [`char::is_digit`] panic.
     #[error("Invalid radix: {0}. Radix must be between 0 and 36 inclusive. See [`char::is_digit`] for details.")]
@@ -159,6 +161,8 @@ pub enum CharMatcherError {
     },
 }
 
+impl ErrorCode for CharMatcherError {}
+
 impl CharMatcher {
     /// # Errors
     /// See each of [`Self`]'s variant's documentation for details.
@@ -9,6 +9,7 @@ use std::sync::OnceLock;
 
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use strum::IntoStaticStr;
 
 use crate::types::*;
 use crate::glue::*;
@@ -23,6 +24,12 @@ mod common_call;
 pub use common_call::*;
 mod commons;
 pub use commons::*;
+mod lint;
+pub use lint::*;
+mod merge;
+pub use merge::*;
+mod output_canon;
+pub use output_canon::*;
 
 /// The rules and rule parameters describing how to modify URLs.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Suitability)]
@@ -42,6 +49,26 @@ pub struct Config {
     /// Various things that are used in multiple spots.
     #[serde(default, skip_serializing_if = "is_default")]
     pub commons: Commons,
+    /// If [`Some`], normalization to apply after all of [`Self::rules`] are applied.
+    ///
+    /// Useful for making outputs stable regardless of which order rules add query parameters in.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// let config: Config = serde_json::from_str(r#"{
+    ///     "canonicalize_output": {"sort_query_params": true},
+    ///     "rules": [
+    ///         {"condition": "Always", "mapper": {"SetPart": {"part": {"QueryParam": "b"}, "value": "2"}}},
+    ///         {"condition": "Always", "mapper": {"SetPart": {"part": {"QueryParam": "a"}, "value": "1"}}}
+    ///     ]
+    /// }"#).unwrap();
+    ///
+    /// url_cleaner::job_state!(job_state;);
+    /// config.apply(&mut job_state).unwrap();
+    /// assert_eq!(job_state.url.as_str(), "https://example.com/?a=1&b=2");
+    /// ```
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub canonicalize_output: Option<OutputCanon>,
     /// The [`Rule`]s that modify the URLS.
     pub rules: Rules
 }
@@ -112,18 +139,26 @@ impl Config {
     }
 
     /// Basic wrapper around [`Self::rules`]'s [`Rules::apply`].
+    ///
+    /// Then, if [`Self::canonicalize_output`] is [`Some`], applies it.
     /// # Errors
     /// If the call to [`Rules::apply`] returns an error, that error is returned.
     #[allow(dead_code, reason = "Public API.")]
     pub fn apply(&self, job_state: &mut JobState) -> Result<(), ApplyConfigError> {
-        self.rules.apply(job_state).map_err(Into::into)
+        self.rules.apply(job_state)?;
+        if let Some(canon) = &self.canonicalize_output {canon.apply(job_state.url);}
+        Ok(())
     }
 
     /// Basic wrapper around [`Self::rules`]'s [`Rules::apply_no_revert`].
+    ///
+    /// Then, if [`Self::canonicalize_output`] is [`Some`], applies it.
     /// # Errors
     /// If the call to [`Rules::apply_no_revert`] returns an error, that error is returned.
     pub fn apply_no_revert(&self, job_state: &mut JobState) -> Result<(), ApplyConfigError> {
-        self.rules.apply_no_revert(job_state).map_err(Into::into)
+        self.rules.apply_no_revert(job_state)?;
+        if let Some(canon) = &self.canonicalize_output {canon.apply(job_state.url);}
+        Ok(())
     }
 
     /// Runs the provided [`Tests`], panicking when any error happens or test fails.
@@ -146,13 +181,22 @@ impl Config {
 /// The enum of errors [`Config::apply`] can return.
 /// 
 /// Exists for future compatibility.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "ApplyConfigError::")]
 pub enum ApplyConfigError {
     /// Returned when a [`RuleError`] is encountered.
     #[error(transparent)]
     RuleError(#[from] RuleError)
 }
 
+impl ErrorCode for ApplyConfigError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::RuleError(e) => e.code(),
+        }
+    }
+}
+
 /// The default [`Config`] as minified JSON.
 ///
 /// When running `cargo test`, the unminified version is used.
@@ -169,7 +213,8 @@ pub const DEFAULT_CONFIG_STR: &str = include_str!("../../default-config.json");
 pub static DEFAULT_CONFIG: OnceLock<Config> = OnceLock::new();
 
 /// An enum containing all possible errors that can happen when loading/parsing a config.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "GetConfigError::")]
 pub enum GetConfigError {
     /// Could not load the config.
     #[error(transparent)]
@@ -179,6 +224,8 @@ pub enum GetConfigError {
     CantParseConfig(#[from] serde_json::Error),
 }
 
+impl ErrorCode for GetConfigError {}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
 mod tests {
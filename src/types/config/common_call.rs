@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use strum::IntoStaticStr;
 
 use crate::types::*;
 use crate::util::*;
@@ -39,22 +40,40 @@ string_or_struct_magic!(CommonCall);
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Suitability)]
 pub struct CommonCallArgsSource {
     /// The flags for a common call.
+    ///
+    /// Defaults to an empty [`HashSet`].
+    #[serde(default, skip_serializing_if = "is_default")]
     pub flags: HashSet<String>,
     /// The vars for a common call.
+    ///
+    /// Defaults to an empty [`HashMap`].
+    #[serde(default, skip_serializing_if = "is_default")]
     pub vars: HashMap<String, StringSource>,
     /// The [`HttpClientConfigDiff`] to use for the duration of a common call.
+    ///
+    /// Defaults to [`None`].
     #[cfg(feature = "http")]
-    pub http_client_config_diff: Option<HttpClientConfigDiff>
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub http_client_config_diff: Option<Box<HttpClientConfigDiff>>
 }
 
 /// The enum of errors that [`CommonCallArgsSource::make`] can return.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "CommonCallArgsError::")]
 pub enum CommonCallArgsError {
     /// Returned when a [`StringSourceError`] is encountered.
     #[error(transparent)]
     StringSourceError(#[from] Box<StringSourceError>)
 }
 
+impl ErrorCode for CommonCallArgsError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::StringSourceError(e) => e.code(),
+        }
+    }
+}
+
 impl From<StringSourceError> for CommonCallArgsError {
     fn from(value: StringSourceError) -> Self {
         Self::StringSourceError(Box::new(value))
@@ -70,7 +89,7 @@ impl CommonCallArgsSource {
             flags: self.flags.iter().map(|x| Cow::Borrowed(&**x)).collect(),
             vars: self.vars.iter().map(|(k, v)| Ok((Cow::Borrowed(&**k), get_string!(v, job_state, StringSourceError)))).collect::<Result<HashMap<_, _>, StringSourceError>>()?,
             #[cfg(feature = "http")]
-            http_client_config_diff: self.http_client_config_diff.as_ref().map(Cow::Borrowed)
+            http_client_config_diff: self.http_client_config_diff.as_deref().map(Cow::Borrowed)
         })
     }
 }
@@ -86,3 +105,15 @@ pub struct CommonCallArgs<'a> {
     #[cfg(feature = "http")]
     pub http_client_config_diff: Option<Cow<'a, HttpClientConfigDiff>>
 }
+
+impl CommonCallArgs<'_> {
+    /// Makes a [`CommonCallArgs`] that doesn't borrow anything.
+    pub fn to_owned_args(&self) -> CommonCallArgs<'static> {
+        CommonCallArgs {
+            flags: self.flags.iter().map(|x| Cow::Owned(x.to_string())).collect(),
+            vars: self.vars.iter().map(|(k, v)| (Cow::Owned(k.to_string()), v.clone())).collect(),
+            #[cfg(feature = "http")]
+            http_client_config_diff: self.http_client_config_diff.as_deref().cloned().map(Cow::Owned)
+        }
+    }
+}
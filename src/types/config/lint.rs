@@ -0,0 +1,194 @@
+//! Basic static analysis of [`Config`]s.
+
+use serde::{Serialize, Deserialize};
+
+use crate::types::*;
+
+/// A problem found by [`Config::lint`].
+///
+/// Each variant's `path` is where in [`Config::rules`] the problem is, such as `rules[2].rules[0]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LintWarning {
+    /// Returned when a [`Rule`] can never do anything because its condition is [`Condition::Never`].
+    NeverRule {
+        /// The path of the [`Rule`].
+        path: String
+    },
+    /// Returned when a [`Rule`] comes after an earlier [`Rule`] in the same [`Rules`] that always replaces the whole URL.
+    ///
+    /// Whatever the [`Rule`] was written to handle has usually already been replaced by then.
+    AfterUrlReplaced {
+        /// The path of the [`Rule`].
+        path: String,
+        /// The path of the earlier [`Rule`] that always replaces the whole URL.
+        after: String
+    },
+    /// Returned when two sibling [`Rule::PartMap`]s, [`Rule::PartRuleMap`]s, and/or [`Rule::PartRulesMap`]s map the same key of the same [`UrlPart`].
+    ///
+    /// Usually this means two entries for the same host that should be merged.
+    DuplicateMapKey {
+        /// The path of the later [`Rule`].
+        path: String,
+        /// The path of the earlier [`Rule`].
+        first: String,
+        /// The [`UrlPart`] both [`Rule`]s map.
+        part: UrlPart,
+        /// The duplicated key.
+        key: String
+    }
+}
+
+impl std::fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NeverRule {path} => write!(f, "{path}: Rule's condition is Never so it never does anything."),
+            Self::AfterUrlReplaced {path, after} => write!(f, "{path}: Rule comes after {after}, which always replaces the whole URL."),
+            Self::DuplicateMapKey {path, first, part, key} => write!(f, "{path}: Key {key:?} of {part:?} is also mapped by {first}.")
+        }
+    }
+}
+
+impl Config {
+    /// Statically checks [`Self::rules`] for obviously dead or redundant rules.
+    ///
+    /// Currently checks for
+    ///
+    /// - [`Rule::Normal`]s and [`Rule::SharedCondition`]s whose condition is [`Condition::Never`],
+    /// - [`Rule`]s after a [`Condition::Always`] [`Rule`] that replaces the whole URL ([`Mapper::SetPart`], [`Mapper::CopyPart`], or [`Mapper::MovePart`] to [`UrlPart::Whole`], or [`Mapper::GetUrlFromQueryParam`]), and
+    /// - Sibling [`Rule::PartMap`]s, [`Rule::PartRuleMap`]s, and [`Rule::PartRulesMap`]s over the same [`UrlPart`] that share a key.
+    ///
+    /// The exact set of checks is unspecified and may grow.
+    pub fn lint(&self) -> Vec<LintWarning> {
+        let mut ret = Vec::new();
+        lint_rules(&self.rules, "rules", &mut ret);
+        ret
+    }
+}
+
+/// Returns [`true`] if `rule` always replaces the whole URL.
+///
+/// That is, if it's a [`Rule::Normal`] with [`Condition::Always`] or a [`Rule::Mapper`] whose [`Mapper`] is
+///
+/// - [`Mapper::SetPart`], [`Mapper::CopyPart`], or [`Mapper::MovePart`] setting [`UrlPart::Whole`], or
+/// - [`Mapper::GetUrlFromQueryParam`].
+fn replaces_whole_url(rule: &Rule) -> bool {
+    match rule {
+        Rule::Normal {condition: Condition::Always, mapper} | Rule::Mapper(mapper) => matches!(mapper,
+            Mapper::SetPart  {part: UrlPart::Whole, ..} |
+            Mapper::CopyPart {to  : UrlPart::Whole, ..} |
+            Mapper::MovePart {to  : UrlPart::Whole, ..} |
+            Mapper::GetUrlFromQueryParam(_)
+        ),
+        _ => false
+    }
+}
+
+/// Lints each [`Rule`] in `rules` and the relationships between them.
+fn lint_rules(rules: &Rules, path: &str, warnings: &mut Vec<LintWarning>) {
+    let mut replaced_at = None;
+    let mut map_keys = Vec::<(&UrlPart, &str, String)>::new();
+
+    for (i, rule) in rules.iter().enumerate() {
+        let rule_path = format!("{path}[{i}]");
+
+        if let Some(after) = &replaced_at {
+            warnings.push(LintWarning::AfterUrlReplaced {path: rule_path.clone(), after: String::clone(after)});
+        }
+        if replaces_whole_url(rule) {
+            replaced_at = Some(rule_path.clone());
+        }
+
+        let keyed = match rule {
+            Rule::PartMap      {part, map} => Some((part, map.map.keys().collect::<Vec<_>>())),
+            Rule::PartRuleMap  {part, map} => Some((part, map.map.keys().collect::<Vec<_>>())),
+            Rule::PartRulesMap {part, map} => Some((part, map.map.keys().collect::<Vec<_>>())),
+            _ => None
+        };
+        if let Some((part, mut keys)) = keyed {
+            keys.sort();
+            for key in keys {
+                match map_keys.iter().find(|(x, y, _)| *x == part && *y == key) {
+                    Some((_, _, first)) => warnings.push(LintWarning::DuplicateMapKey {path: rule_path.clone(), first: first.clone(), part: part.clone(), key: key.clone()}),
+                    None => map_keys.push((part, key, rule_path.clone()))
+                }
+            }
+        }
+
+        lint_rule(rule, &rule_path, warnings);
+    }
+}
+
+/// Lints `rule` and any [`Rule`]s it contains.
+fn lint_rule(rule: &Rule, path: &str, warnings: &mut Vec<LintWarning>) {
+    match rule {
+        Rule::Normal {condition: Condition::Never, ..} | Rule::SharedCondition {condition: Condition::Never, ..} => warnings.push(LintWarning::NeverRule {path: path.into()}),
+        Rule::Tagged {rule, ..} => lint_rule(rule, &format!("{path}.rule"), warnings),
+        Rule::SharedCondition {rules, ..} | Rule::Repeat {rules, ..} | Rule::Rules(rules) | Rule::FirstMatch(rules) => lint_rules(rules, &format!("{path}.rules"), warnings),
+        Rule::PartRuleMap   {map, ..} | Rule::StringRuleMap  {map, ..} => for (key, rule ) in sorted(&map.map) {lint_rule (rule , &format!("{path}.map[{key:?}]"), warnings)},
+        Rule::PartRulesMap  {map, ..} | Rule::StringRulesMap {map, ..} => for (key, rules) in sorted(&map.map) {lint_rules(rules, &format!("{path}.map[{key:?}]"), warnings)},
+        _ => {}
+    }
+}
+
+/// Gets the entries of `map` sorted by key, to make [`Config::lint`]'s output deterministic.
+fn sorted<T>(map: &std::collections::HashMap<String, T>) -> Vec<(&String, &T)> {
+    let mut ret = map.iter().collect::<Vec<_>>();
+    ret.sort_by_key(|(key, _)| *key);
+    ret
+}
+
+#[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lint_never_rule() {
+        let config: Config = serde_json::from_value(serde_json::json!({"rules": [
+            {"condition": "Always", "mapper": "None"},
+            {"SharedCondition": {"condition": "Always", "rules": [
+                {"condition": "Never", "mapper": "RemoveQuery"}
+            ]}}
+        ]})).unwrap();
+
+        assert_eq!(config.lint(), vec![LintWarning::NeverRule {path: "rules[1].rules[0]".into()}]);
+    }
+
+    #[test]
+    fn lint_duplicate_map_key() {
+        let config: Config = serde_json::from_value(serde_json::json!({"rules": [
+            {"PartMap": {"part": "Host", "map": {"example.com": "RemoveQuery", "a.com": "None"}}},
+            {"PartMap": {"part": "Path", "map": {"example.com": "RemoveQuery"}}},
+            {"PartMap": {"part": "Host", "map": {"example.com": "None"}}}
+        ]})).unwrap();
+
+        assert_eq!(config.lint(), vec![LintWarning::DuplicateMapKey {
+            path: "rules[2]".into(),
+            first: "rules[0]".into(),
+            part: UrlPart::Host,
+            key: "example.com".into()
+        }]);
+    }
+
+    #[test]
+    fn lint_after_url_replaced() {
+        let config: Config = serde_json::from_value(serde_json::json!({"rules": [
+            {"condition": "Always", "mapper": "RemoveQuery"},
+            {"condition": "Always", "mapper": {"GetUrlFromQueryParam": "u"}},
+            {"condition": "Never", "mapper": "RemoveQuery"},
+            {"Rules": [
+                {"condition": "Always", "mapper": {"SetPart": {"part": "Whole", "value": "https://example.com"}}},
+                {"condition": "Always", "mapper": "RemoveQuery"}
+            ]},
+            {"condition": "Always", "mapper": {"SetPart": {"part": "Path", "value": "/"}}}
+        ]})).unwrap();
+
+        assert_eq!(config.lint(), vec![
+            LintWarning::AfterUrlReplaced {path: "rules[2]".into(), after: "rules[1]".into()},
+            LintWarning::NeverRule {path: "rules[2]".into()},
+            LintWarning::AfterUrlReplaced {path: "rules[3]".into(), after: "rules[1]".into()},
+            LintWarning::AfterUrlReplaced {path: "rules[3].rules[1]".into(), after: "rules[3].rules[0]".into()},
+            LintWarning::AfterUrlReplaced {path: "rules[4]".into(), after: "rules[1]".into()}
+        ]);
+    }
+}
@@ -0,0 +1,228 @@
+//! Layering multiple [`Config`]s into one.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::types::*;
+use crate::glue::*;
+
+/// The enum of errors [`Config::merge`] can return.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MergeConfigError {
+    /// Returned when both [`Config`]s' [`Commons`] have an entry with the same name in the same namespace.
+    #[error("Both configs have a common {namespace} named {name:?}.")]
+    CommonNameCollision {
+        /// The namespace of the collision, like `rules` or `string_sources`.
+        namespace: &'static str,
+        /// The name both [`Config`]s use.
+        name: String
+    }
+}
+
+impl Config {
+    /// Layers `other` on top of `self`.
+    ///
+    /// - [`Self::rules`]: `other`'s rules are appended after `self`'s.
+    /// - [`Self::commons`]: Each namespace is merged. If both have an entry with the same name in the same namespace, returns the error [`MergeConfigError::CommonNameCollision`].
+    /// - [`Self::params`]: See [`Params::merge`].
+    /// - [`Self::docs`]: See [`ConfigDocs::merge`].
+    /// - [`Self::canonicalize_output`]: If `other`'s is [`Some`], it replaces `self`'s.
+    /// - `cache_path`: If `other`'s isn't the default, it replaces `self`'s.
+    ///
+    /// Name collisions are checked before anything is changed, so if this returns an error `self` is left unchanged.
+    ///
+    /// Unlike [`ParamsDiff`], which only changes [`Self::params`], this is for combining, for example, a base config and site specific overlays.
+    /// # Errors
+    /// If both [`Self::commons`] have an entry with the same name in the same namespace, returns the error [`MergeConfigError::CommonNameCollision`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// let mut base: Config = serde_json::from_str(r#"{
+    ///     "params": {"flags": ["a"], "vars": {"x": "1", "y": "1"}},
+    ///     "rules": [{"condition": "Always", "mapper": {"RemoveQueryParams": ["utm_source"]}}]
+    /// }"#).unwrap();
+    /// let overlay: Config = serde_json::from_str(r#"{
+    ///     "params": {"flags": ["b"], "vars": {"y": "2"}},
+    ///     "rules": [{"condition": "Always", "mapper": {"RemoveQueryParams": ["ref"]}}]
+    /// }"#).unwrap();
+    ///
+    /// base.merge(overlay).unwrap();
+    ///
+    /// assert_eq!(base.rules.len(), 2);
+    /// assert!(base.params.flags.contains("a") && base.params.flags.contains("b"));
+    /// assert_eq!(base.params.vars.get("x").map(String::as_str), Some("1"));
+    /// assert_eq!(base.params.vars.get("y").map(String::as_str), Some("2"));
+    ///
+    /// url_cleaner::job_state!(job_state; url = "https://example.com?utm_source=a&ref=b&c=d";);
+    /// base.apply(&mut job_state).unwrap();
+    /// assert_eq!(job_state.url.as_str(), "https://example.com/?c=d");
+    /// ```
+    pub fn merge(&mut self, other: Config) -> Result<(), MergeConfigError> {
+        self.commons.merge(other.commons)?;
+        self.params.merge(other.params);
+        self.docs.merge(other.docs);
+        if other.canonicalize_output.is_some() {self.canonicalize_output = other.canonicalize_output;}
+        #[cfg(feature = "cache")]
+        if other.cache_path != CachePath::default() {self.cache_path = other.cache_path;}
+        self.rules.0.extend(other.rules.0);
+        Ok(())
+    }
+}
+
+impl Commons {
+    /// Moves all of `other`'s entries into `self`.
+    ///
+    /// Used by [`Config::merge`].
+    /// # Errors
+    /// If both have an entry with the same name in the same namespace, returns the error [`MergeConfigError::CommonNameCollision`] and leaves `self` unchanged.
+    pub fn merge(&mut self, other: Commons) -> Result<(), MergeConfigError> {
+        check_collisions("rules"               , &self.rules               , &other.rules               )?;
+        check_collisions("conditions"          , &self.conditions          , &other.conditions          )?;
+        check_collisions("mappers"             , &self.mappers             , &other.mappers             )?;
+        check_collisions("string_sources"      , &self.string_sources      , &other.string_sources      )?;
+        check_collisions("string_modifications", &self.string_modifications, &other.string_modifications)?;
+        check_collisions("string_matchers"     , &self.string_matchers     , &other.string_matchers     )?;
+
+        self.rules               .extend(other.rules               );
+        self.conditions          .extend(other.conditions          );
+        self.mappers             .extend(other.mappers             );
+        self.string_sources      .extend(other.string_sources      );
+        self.string_modifications.extend(other.string_modifications);
+        self.string_matchers     .extend(other.string_matchers     );
+        Ok(())
+    }
+}
+
+/// If `a` and `b` share a key, returns the error [`MergeConfigError::CommonNameCollision`].
+///
+/// Keys are checked in sorted order to make the error deterministic.
+fn check_collisions<T>(namespace: &'static str, a: &HashMap<String, T>, b: &HashMap<String, T>) -> Result<(), MergeConfigError> {
+    match b.keys().filter(|name| a.contains_key(*name)).min() {
+        Some(name) => Err(MergeConfigError::CommonNameCollision {namespace, name: name.clone()}),
+        None => Ok(())
+    }
+}
+
+impl Params {
+    /// Layers `other` on top of `self`.
+    ///
+    /// - [`Self::flags`] are unioned.
+    /// - [`Self::vars`], [`Self::lists`], and [`Self::named_partitionings`] are unioned, with `other`'s taking precedence.
+    /// - [`Self::sets`] with the same name are unioned.
+    /// - [`Self::maps`] with the same name have their [`Map::map`]s unioned with `other`'s taking precedence. `other`'s [`Map::if_null`] and [`Map::r#else`] replace `self`'s if they're [`Some`].
+    /// - [`Self::rule_filter`]'s [`RuleFilter::only_tags`] and [`RuleFilter::skip_tags`] are unioned.
+    /// - `read_cache` and `write_cache` are only [`true`] if they're [`true`] in both, since they default to [`true`].
+    /// - If `other`'s `http_client_config` isn't the default, it replaces `self`'s.
+    ///
+    /// Used by [`Config::merge`].
+    pub fn merge(&mut self, other: Params) {
+        self.flags.extend(other.flags);
+        self.vars .extend(other.vars );
+        for (name, set) in other.sets {
+            self.sets.entry(name).or_default().extend(set);
+        }
+        self.lists.extend(other.lists);
+        for (name, map) in other.maps {
+            match self.maps.get_mut(&name) {
+                Some(existing) => {
+                    existing.map.extend(map.map);
+                    if map.if_null.is_some() {existing.if_null = map.if_null;}
+                    if map.r#else .is_some() {existing.r#else  = map.r#else ;}
+                },
+                None => {let _ = self.maps.insert(name, map);}
+            }
+        }
+        self.named_partitionings.extend(other.named_partitionings);
+        self.rule_filter.only_tags.extend(other.rule_filter.only_tags);
+        self.rule_filter.skip_tags.extend(other.rule_filter.skip_tags);
+        #[cfg(feature = "cache")]
+        {
+            self.read_cache  &= other.read_cache;
+            self.write_cache &= other.write_cache;
+        }
+        #[cfg(feature = "http")]
+        if other.http_client_config != HttpClientConfig::default() {self.http_client_config = other.http_client_config;}
+    }
+}
+
+impl ConfigDocs {
+    /// Layers `other` on top of `self`.
+    ///
+    /// Each map of docs is unioned with `other`'s taking precedence. If `other`'s [`Self::description`] is [`Some`], it's appended to `self`'s.
+    ///
+    /// Used by [`Config::merge`].
+    pub fn merge(&mut self, other: ConfigDocs) {
+        if let Some(description) = other.description {self.description.get_or_insert_default().extend(description);}
+        self.flags              .extend(other.flags              );
+        self.vars               .extend(other.vars               );
+        self.environment_vars   .extend(other.environment_vars   );
+        self.sets               .extend(other.sets               );
+        self.lists              .extend(other.lists              );
+        self.maps               .extend(other.maps               );
+        self.named_partitionings.extend(other.named_partitionings);
+        self.job_context .vars.extend(other.job_context .vars);
+        self.jobs_context.vars.extend(other.jobs_context.vars);
+    }
+}
+
+#[allow(clippy::unwrap_used, reason = "Panicking tests are easier to write than erroring tests.")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_rules_and_commons() {
+        let mut base: Config = serde_json::from_value(serde_json::json!({
+            "commons": {"mappers": {"remove_utm": {"RemoveQueryParams": ["utm_source"]}}},
+            "rules": [{"condition": "Always", "mapper": {"Common": {"name": "remove_utm"}}}]
+        })).unwrap();
+        let overlay: Config = serde_json::from_value(serde_json::json!({
+            "commons": {"mappers": {"remove_ref": {"RemoveQueryParams": ["ref"]}}},
+            "rules": [{"condition": "Always", "mapper": {"Common": {"name": "remove_ref"}}}]
+        })).unwrap();
+
+        base.merge(overlay).unwrap();
+
+        assert_eq!(base.rules.len(), 2);
+        assert!(base.commons.mappers.contains_key("remove_utm"));
+        assert!(base.commons.mappers.contains_key("remove_ref"));
+
+        let mut url = BetterUrl::parse("https://example.com?utm_source=a&ref=b&c=d").unwrap();
+        #[cfg(feature = "http")]
+        let rate_limiter = Default::default();
+        #[cfg(feature = "cache")]
+        let cache = Default::default();
+        base.apply(&mut JobState {
+            url: &mut url,
+            scratchpad: &mut Default::default(),
+            common_args: None,
+            context: &Default::default(),
+            jobs_context: &Default::default(),
+            params: &base.params,
+            commons: &base.commons,
+            #[cfg(feature = "cache")]
+            cache: &cache,
+            #[cfg(feature = "http")]
+            rate_limiter: &rate_limiter,
+            trace: None
+        }).unwrap();
+        assert_eq!(url.as_str(), "https://example.com/?c=d");
+    }
+
+    #[test]
+    fn merge_commons_collision() {
+        let mut base: Config = serde_json::from_value(serde_json::json!({
+            "commons": {"conditions": {"a": "Always", "b": "Never"}},
+            "rules": [{"condition": "Always", "mapper": "RemoveQuery"}]
+        })).unwrap();
+        let original = base.clone();
+        let overlay: Config = serde_json::from_value(serde_json::json!({
+            "commons": {"mappers": {"c": "None"}, "conditions": {"b": "Always"}},
+            "rules": [{"condition": "Always", "mapper": "RemoveEmptyFragment"}]
+        })).unwrap();
+
+        assert_eq!(base.merge(overlay), Err(MergeConfigError::CommonNameCollision {namespace: "conditions", name: "b".into()}));
+        assert_eq!(base, original);
+    }
+}
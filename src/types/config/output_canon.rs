@@ -0,0 +1,67 @@
+//! Provides [`OutputCanon`] which makes [`Config`] outputs stable.
+
+use serde::{Serialize, Deserialize};
+
+use crate::types::*;
+use crate::util::*;
+
+/// Normalization applied to a URL after all of a [`Config`]'s [`Rule`]s are applied.
+///
+/// Used by [`Config::canonicalize_output`] to make outputs stable regardless of the order rules added things in, without every config having to end with the same rules.
+///
+/// Each option defaults to [`false`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Suitability)]
+pub struct OutputCanon {
+    /// Sort the query parameters by name.
+    ///
+    /// The sort is stable, so parameters with the same name keep their relative order.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub sort_query_params: bool,
+    /// If the URL's port is the default port for its scheme, remove it.
+    ///
+    /// See [`BetterUrl::remove_default_port`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub remove_default_port: bool,
+    /// If the URL's query has no parameters (`https://example.com?`), remove it.
+    ///
+    /// See [`Mapper::RemoveEmptyQuery`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub remove_empty_query: bool,
+    /// If the URL's fragment is empty (`https://example.com#`), remove it.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub remove_empty_fragment: bool
+}
+
+impl OutputCanon {
+    /// Applies the normalizations specified by `self` to `url`.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// let canon = OutputCanon {sort_query_params: true, remove_default_port: true, remove_empty_query: true, remove_empty_fragment: true};
+    ///
+    /// let mut url = BetterUrl::parse("https://example.com/?c=1&a=2&b=3&a=1").unwrap();
+    /// canon.apply(&mut url);
+    /// assert_eq!(url.as_str(), "https://example.com/?a=2&a=1&b=3&c=1");
+    ///
+    /// let mut url = BetterUrl::parse("git://example.com:9418/a?#").unwrap();
+    /// canon.apply(&mut url);
+    /// assert_eq!(url.as_str(), "git://example.com/a");
+    /// ```
+    pub fn apply(&self, url: &mut BetterUrl) {
+        if self.sort_query_params && let Some(query) = url.query() {
+            let mut pairs = query.split('&').collect::<Vec<_>>();
+            pairs.sort_by_key(|pair| pair.split_once('=').map_or(*pair, |(name, _)| name));
+            let new_query = pairs.join("&");
+            url.set_query(Some(&new_query));
+        }
+        if self.remove_default_port {
+            url.remove_default_port();
+        }
+        if self.remove_empty_query && url.query().is_some() && url.query_pairs().next().is_none() {
+            url.set_query(None);
+        }
+        if self.remove_empty_fragment && url.fragment() == Some("") {
+            url.set_fragment(None);
+        }
+    }
+}
@@ -38,6 +38,9 @@ pub struct Params {
     #[cfg(feature = "cache")]
     #[serde(default = "get_true", skip_serializing_if = "is_true")]
     pub write_cache: bool,
+    /// Decides which [`Rule::Normal`]s to run based on their tags.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub rule_filter: RuleFilter,
     /// The default headers to send in HTTP requests.
     #[cfg(feature = "http")]
     #[serde(default, skip_serializing_if = "is_default")]
@@ -54,6 +57,7 @@ impl Default for Params {
             lists: HashMap::default(),
             maps : HashMap::default(),
             named_partitionings: HashMap::default(),
+            rule_filter: RuleFilter::default(),
             #[cfg(feature = "cache")] read_cache: true,
             #[cfg(feature = "cache")] write_cache: true,
             #[cfg(feature = "http")]
@@ -87,6 +91,10 @@ pub struct ParamsDiff {
     #[serde(default, skip_serializing_if = "is_default")] pub map_diffs: HashMap<String, MapDiff<String>>,
     /// If the maps exist in [`Params::maps`], remove them.
     #[serde(default, skip_serializing_if = "is_default")] pub delete_maps: Vec<String>,
+    /// Adds to [`Params::rule_filter`]'s [`RuleFilter::only_tags`]. Defaults to an empty [`HashSet`].
+    #[serde(default, skip_serializing_if = "is_default")] pub only_tags: HashSet<String>,
+    /// Adds to [`Params::rule_filter`]'s [`RuleFilter::skip_tags`]. Defaults to an empty [`HashSet`].
+    #[serde(default, skip_serializing_if = "is_default")] pub skip_tags: HashSet<String>,
     /// If [`Some`], sets [`Params::read_cache`]. Defaults to [`None`].
     #[cfg(feature = "cache")]
     #[serde(default, skip_serializing_if = "is_default")] pub read_cache : Option<bool>,
@@ -112,9 +120,11 @@ impl ParamsDiff {
     /// 9. Initializes all maps specified by [`Self::init_maps`] to [`HashSet::default`] if they don't exist.
     /// 10. Applies all [`Self::map_diffs`].
     /// 11. Deletes all maps specified in [`Self::delete_maps`].
-    /// 12. If [`Self::read_cache`] is [`Some`], sets `to.read_cache` to the contained value.
-    /// 13. If [`Self::write_cache`] is [`Some`], sets `to.write_cache` to the contained value.
-    /// 14. If [`Self::http_client_config_diff`] is [`Some`], calls [`HttpClientConfigDiff::apply`] with `to.http_client_config`.
+    /// 12. Extends `to.rule_filter.only_tags` with [`Self::only_tags`].
+    /// 13. Extends `to.rule_filter.skip_tags` with [`Self::skip_tags`].
+    /// 14. If [`Self::read_cache`] is [`Some`], sets `to.read_cache` to the contained value.
+    /// 15. If [`Self::write_cache`] is [`Some`], sets `to.write_cache` to the contained value.
+    /// 16. If [`Self::http_client_config_diff`] is [`Some`], calls [`HttpClientConfigDiff::apply`] with `to.http_client_config`.
     pub fn apply(self, to: &mut Params) {
         #[cfg(feature = "debug")]
         let old_to = to.clone();
@@ -154,6 +164,9 @@ impl ParamsDiff {
             to.maps.remove(&k);
         }
 
+        to.rule_filter.only_tags.extend(self.only_tags);
+        to.rule_filter.skip_tags.extend(self.skip_tags);
+
         #[cfg(feature = "cache")] if let Some(read_cache ) = self.read_cache  {to.read_cache  = read_cache ;}
         #[cfg(feature = "cache")] if let Some(write_cache) = self.write_cache {to.write_cache = write_cache;}
 
@@ -191,6 +204,12 @@ pub struct ParamsDiffArgParser {
     /// For each occurrence of this option, its first argument is the map name, and subsequent arguments are the keys to remove.
     #[arg(             long, num_args(1..), value_names = ["NAME", "KEY1"])]
     pub remove_from_map: Vec<Vec<String>>,
+    /// Only run tagged rules with at least one of these tags. Untagged rules always run.
+    #[arg(             long, value_names = ["TAG"])]
+    pub only_tags: Vec<String>,
+    /// Don't run rules with any of these tags.
+    #[arg(             long, value_names = ["TAG"])]
+    pub skip_tags: Vec<String>,
     /// Read stuff from caches. Default value is controlled by the config. Omitting a value means true.
     #[cfg(feature = "cache")]
     #[arg(             long, num_args(0..=1), default_missing_value("true"))]
@@ -305,6 +324,8 @@ impl TryFrom<ParamsDiffArgParser> for ParamsDiff {
                 ret
             },
             delete_maps     : Default::default(),
+            only_tags       : value.only_tags.into_iter().collect(),
+            skip_tags       : value.skip_tags.into_iter().collect(),
             #[cfg(feature = "cache")] read_cache : value.read_cache,
             #[cfg(feature = "cache")] write_cache: value.write_cache,
             #[cfg(feature = "http")] http_client_config_diff: Some(HttpClientConfigDiff {
@@ -326,6 +347,6 @@ impl ParamsDiffArgParser {
         #[cfg(feature = "cache")] #[allow(clippy::unnecessary_operation, reason = "False positive.")] {feature_flag_make_params_diff = feature_flag_make_params_diff || self.read_cache.is_some()};
         #[cfg(feature = "cache")] #[allow(clippy::unnecessary_operation, reason = "False positive.")] {feature_flag_make_params_diff = feature_flag_make_params_diff || self.write_cache.is_some()};
         #[cfg(feature = "http" )] #[allow(clippy::unnecessary_operation, reason = "False positive.")] {feature_flag_make_params_diff = feature_flag_make_params_diff || self.proxy.is_some()};
-        !self.flag.is_empty() || !self.unflag.is_empty() || !self.var.is_empty() || !self.unvar.is_empty() || !self.insert_into_set.is_empty() || !self.remove_from_set.is_empty() || !self.insert_into_map.is_empty() || !self.remove_from_map.is_empty() || feature_flag_make_params_diff
+        !self.flag.is_empty() || !self.unflag.is_empty() || !self.var.is_empty() || !self.unvar.is_empty() || !self.insert_into_set.is_empty() || !self.remove_from_set.is_empty() || !self.insert_into_map.is_empty() || !self.remove_from_map.is_empty() || !self.only_tags.is_empty() || !self.skip_tags.is_empty() || feature_flag_make_params_diff
     }
 }
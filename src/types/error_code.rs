@@ -0,0 +1,29 @@
+//! [`ErrorCode`], stable machine readable names for errors.
+
+#[expect(unused_imports, reason = "Used in a doc comment.")]
+use crate::types::*;
+
+/// Gives errors a stable, machine readable name, like `"UrlPartGetError::PartIsNone"`.
+///
+/// Codes are the error's type name and variant name, so the same variant name in different error types gets different codes.
+///
+/// Errors that wrap another error with an [`ErrorCode`] return the wrapped error's code, so a [`JobError`] caused by [`Mapper::Error`] has the code `"MapperError::ExplicitError"`.
+/// # Examples
+/// ```
+/// # use url_cleaner::types::*;
+/// assert_eq!(MapperError::ExplicitError.code(), "MapperError::ExplicitError");
+/// assert_eq!(ConditionError::ExplicitError.code(), "ConditionError::ExplicitError");
+///
+/// assert_eq!(RuleError::from(MapperError::ExplicitError).code(), "MapperError::ExplicitError");
+/// assert_eq!(RuleError::StringSourceIsNone.code(), "RuleError::StringSourceIsNone");
+///
+/// assert_eq!(RuleError::from(StringSourceError::from(StringModificationError::ExplicitError)).code(), "StringModificationError::ExplicitError");
+/// ```
+pub trait ErrorCode where for<'a> &'a Self: Into<&'static str> {
+    /// The code of the error.
+    ///
+    /// Defaults to the name of the variant, as made by [`strum::IntoStaticStr`].
+    fn code(&self) -> &'static str {
+        self.into()
+    }
+}
@@ -0,0 +1,80 @@
+//! Provides [`HostDetailsField`] which allows getting facets of a [`BetterUrl`]'s [`HostDetails`] as strings.
+
+use std::borrow::Cow;
+
+use serde::{Serialize, Deserialize};
+
+use crate::types::*;
+use crate::util::*;
+
+/// A facet of a [`BetterUrl`]'s [`HostDetails`] that can be gotten as a string.
+///
+/// Facets that are booleans are gotten as `"true"` or `"false"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Suitability)]
+pub enum HostDetailsField {
+    /// The registerable domain, like `example.co.uk` for `www.example.co.uk`.
+    ///
+    /// [`None`] if the host isn't a domain or doesn't have a registerable domain.
+    RegDomain,
+    /// The subdomain, like `www` for `www.example.co.uk`.
+    ///
+    /// [`None`] if the host isn't a domain or doesn't have a subdomain.
+    Subdomain,
+    /// The domain suffix, like `co.uk` for `www.example.co.uk`.
+    ///
+    /// [`None`] if the host isn't a domain or doesn't have a suffix.
+    DomainSuffix,
+    /// Whether or not the host is an IP address.
+    IsIp,
+    /// Whether or not the host is a fully qualified domain name (ends in a `.`).
+    ///
+    /// Always `"false"` for IP addresses.
+    IsFqdn
+}
+
+impl HostDetailsField {
+    /// Gets the facet of `url`'s host specified by `self`.
+    ///
+    /// If `url` doesn't have a host, returns [`None`].
+    /// # Examples
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use url_cleaner::types::*;
+    /// let url = BetterUrl::parse("https://www.example.co.uk").unwrap();
+    ///
+    /// assert_eq!(HostDetailsField::RegDomain   .get(&url), Some(Cow::Borrowed("example.co.uk")));
+    /// assert_eq!(HostDetailsField::Subdomain   .get(&url), Some(Cow::Borrowed("www")));
+    /// assert_eq!(HostDetailsField::DomainSuffix.get(&url), Some(Cow::Borrowed("co.uk")));
+    /// assert_eq!(HostDetailsField::IsIp        .get(&url), Some(Cow::Borrowed("false")));
+    /// assert_eq!(HostDetailsField::IsFqdn      .get(&url), Some(Cow::Borrowed("false")));
+    ///
+    /// let url = BetterUrl::parse("https://www.example.co.uk.").unwrap();
+    /// assert_eq!(HostDetailsField::RegDomain   .get(&url), Some(Cow::Borrowed("example.co.uk")));
+    /// assert_eq!(HostDetailsField::IsFqdn      .get(&url), Some(Cow::Borrowed("true")));
+    ///
+    /// let url = BetterUrl::parse("https://127.0.0.1").unwrap();
+    /// assert_eq!(HostDetailsField::RegDomain   .get(&url), None);
+    /// assert_eq!(HostDetailsField::IsIp        .get(&url), Some(Cow::Borrowed("true")));
+    /// assert_eq!(HostDetailsField::IsFqdn      .get(&url), Some(Cow::Borrowed("false")));
+    ///
+    /// let url = BetterUrl::parse("mailto:a@example.com").unwrap();
+    /// assert_eq!(HostDetailsField::IsIp        .get(&url), None);
+    /// ```
+    pub fn get<'a>(&self, url: &'a BetterUrl) -> Option<Cow<'a, str>> {
+        let (host, details) = url.host_and_details()?;
+        Some(match (self, details) {
+            (Self::RegDomain   , HostDetails::Domain(domain_details)) => Cow::Borrowed(host.get(domain_details.reg_domain_bounds()?)?),
+            (Self::Subdomain   , HostDetails::Domain(domain_details)) => Cow::Borrowed(host.get(domain_details.subdomain_bounds ()?)?),
+            (Self::DomainSuffix, HostDetails::Domain(domain_details)) => Cow::Borrowed(host.get(domain_details.suffix_bounds    ()?)?),
+            (Self::RegDomain | Self::Subdomain | Self::DomainSuffix, _) => None?,
+            (Self::IsIp  , _) => bool_str(!matches!(details, HostDetails::Domain(_))),
+            (Self::IsFqdn, HostDetails::Domain(domain_details)) => bool_str(domain_details.is_fqdn()),
+            (Self::IsFqdn, _) => bool_str(false)
+        })
+    }
+}
+
+/// Gets `value` as a [`Cow<str>`].
+fn bool_str(value: bool) -> Cow<'static, str> {
+    Cow::Borrowed(if value {"true"} else {"false"})
+}
@@ -14,3 +14,5 @@ mod job_context;
 pub use job_context::*;
 mod job_scratchpad;
 pub use job_scratchpad::*;
+mod job_trace;
+pub use job_trace::*;
@@ -2,6 +2,7 @@
 
 use url::Url;
 use thiserror::Error;
+use strum::IntoStaticStr;
 
 use crate::types::*;
 use crate::glue::*;
@@ -19,14 +20,33 @@ pub struct Job<'a> {
     pub jobs_context: &'a JobsContext,
     /// The cache to use.
     #[cfg(feature = "cache")]
-    pub cache: &'a Cache
+    pub cache: &'a Cache,
+    /// The per-host HTTP rate limiter to use.
+    #[cfg(feature = "http")]
+    pub rate_limiter: &'a RateLimiter
 }
 
 impl Job<'_> {
     /// Does the job and returns the resulting [`Url`].
     /// # Errors
     /// If the call to [`Rules::apply`] returns an error, that error is returned.
-    pub fn r#do(mut self) -> Result<Url, DoJobError> {
+    pub fn r#do(self) -> Result<Url, DoJobError> {
+        self.do_with_trace(None)
+    }
+
+    /// [`Self::do`] but also returns the [`TraceEvent`]s recorded by each [`Rule`].
+    ///
+    /// See [`JobState::trace`] for details.
+    pub fn do_traced(self) -> (Result<Url, DoJobError>, Vec<TraceEvent>) {
+        let mut trace = Vec::new();
+        let ret = self.do_with_trace(Some(&mut trace));
+        (ret, trace)
+    }
+
+    /// Does the job, recording [`TraceEvent`]s into `trace` if it's [`Some`].
+    /// # Errors
+    /// If the call to [`Config::apply_no_revert`] returns an error, that error is returned.
+    fn do_with_trace(mut self, trace: Option<&mut Vec<TraceEvent>>) -> Result<Url, DoJobError> {
         self.config.apply_no_revert(&mut JobState {
             url: &mut self.url,
             params: &self.config.params,
@@ -35,16 +55,29 @@ impl Job<'_> {
             jobs_context: self.jobs_context,
             #[cfg(feature = "cache")]
             cache: self.cache,
+            #[cfg(feature = "http")]
+            rate_limiter: self.rate_limiter,
             commons: &self.config.commons,
-            common_args: None
+            common_args: None,
+            trace
         })?;
         Ok(self.url.into())
     }
 }
 
 /// The enums of error [`Job::do`] can return.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "DoJobError::")]
 pub enum DoJobError {
     /// Returned when a [`ApplyConfigError`] is encountered.
     #[error(transparent)] ApplyConfigError(#[from] ApplyConfigError)
 }
+
+impl ErrorCode for DoJobError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::ApplyConfigError(e) => e.code(),
+        }
+    }
+}
+
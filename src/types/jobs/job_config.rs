@@ -7,6 +7,7 @@ use std::io;
 use serde::{Serialize, Deserialize};
 use url::Url;
 use thiserror::Error;
+use strum::IntoStaticStr;
 
 use crate::types::*;
 use crate::util::*;
@@ -61,7 +62,8 @@ impl From<BetterUrl> for JobConfig {
 /// The enum of errors [`JobConfig::from_str`] and [`<JobConfig as TryFrom<&str>>::try_from`] can return.
 /// 
 /// Additionally has [`Self::IoError`] and [`Self::Other`] to accommodate [`Jobs::job_configs_source`] iterators.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "MakeJobConfigError::")]
 pub enum MakeJobConfigError {
     /// Returned when a [`url::ParseError`] is encountered.
     #[error(transparent)]
@@ -77,6 +79,8 @@ pub enum MakeJobConfigError {
     Other(#[from] Box<dyn Error + Send>)
 }
 
+impl ErrorCode for MakeJobConfigError {}
+
 impl FromStr for JobConfig {
     type Err = MakeJobConfigError;
 
@@ -92,6 +96,41 @@ impl FromStr for JobConfig {
     }
 }
 
+impl JobConfig {
+    /// [`Self::from_str`], but if `s` is a URL without a scheme, `scheme` is added.
+    ///
+    /// Protocol relative URLs like `//example.com/x` become `scheme:` followed by the URL. Other URLs that [`Url::parse`] says are relative, like `example.com/x`, become `scheme://` followed by the URL.
+    ///
+    /// Please note that inputs like `example.com:8080/x` are valid URLs with the scheme `example.com` and are therefore returned as is.
+    /// # Errors
+    /// If `s` starts with `{` or `"` and the call to [`serde_json::from_str`] returns an error, that error is returned.
+    ///
+    /// If the call to [`Url::parse`] returns an error other than [`url::ParseError::RelativeUrlWithoutBase`], that error is returned.
+    ///
+    /// If the call to [`Url::parse`] with `scheme` added returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// assert_eq!(JobConfig::from_str_with_default_scheme("//example.com/x"   , "https").unwrap().url.as_str(), "https://example.com/x");
+    /// assert_eq!(JobConfig::from_str_with_default_scheme("example.com/x"     , "https").unwrap().url.as_str(), "https://example.com/x");
+    /// assert_eq!(JobConfig::from_str_with_default_scheme("x.com"             , "http" ).unwrap().url.as_str(), "http://x.com/");
+    /// assert_eq!(JobConfig::from_str_with_default_scheme("http://example.com", "https").unwrap().url.as_str(), "http://example.com/");
+    /// assert_eq!(JobConfig::from_str_with_default_scheme(r#"{"url": "https://example.com"}"#, "http").unwrap().url.as_str(), "https://example.com/");
+    ///
+    /// JobConfig::from_str_with_default_scheme("//", "https").unwrap_err();
+    /// ```
+    pub fn from_str_with_default_scheme(s: &str, scheme: &str) -> Result<Self, MakeJobConfigError> {
+        match Self::from_str(s) {
+            Err(MakeJobConfigError::UrlParseError(url::ParseError::RelativeUrlWithoutBase)) => Ok(if s.starts_with("//") {
+                Url::parse(&format!("{scheme}:{s}"))?
+            } else {
+                Url::parse(&format!("{scheme}://{s}"))?
+            }.into()),
+            x => x
+        }
+    }
+}
+
 impl TryFrom<&str> for JobConfig {
     type Error = <Self as FromStr>::Err;
 
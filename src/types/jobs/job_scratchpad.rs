@@ -1,9 +1,11 @@
 //! Scratchpad space for rules to store state in.
 
 use std::collections::{HashSet, HashMap};
+use std::sync::{Mutex, PoisonError};
 
 use serde::{Serialize, Deserialize};
 
+use crate::types::*;
 use crate::util::*;
 
 /// Mutable state that you can use to track data between rules outside of the URL.
@@ -14,5 +16,60 @@ pub struct JobScratchpad {
     pub flags: HashSet<String>,
     /// String variables used to determine behavior.
     #[serde(default, skip_serializing_if = "is_default")]
-    pub vars: HashMap<String, String>
+    pub vars: HashMap<String, String>,
+    /// The values memoized by [`StringSource::Memoized`].
+    #[serde(skip)]
+    pub memos: Memos
 }
+
+/// The values memoized by [`StringSource::Memoized`].
+///
+/// Each value is keyed by the memoized [`StringSource`] and the [`CommonCallArgs`] it was gotten with, so equal [`StringSource`]s in different parts of a config share values.
+///
+/// Uses a [`Mutex`] because [`StringSource::get`] only gets an immutable [`JobScratchpad`].
+#[derive(Debug, Default)]
+pub struct Memos(Mutex<Vec<Memo>>);
+
+/// A value memoized by [`StringSource::Memoized`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Memo {
+    /// A copy of the memoized [`StringSource`].
+    source: StringSource,
+    /// The [`CommonCallArgs`] the value was gotten with.
+    common_args: Option<CommonCallArgs<'static>>,
+    /// The value.
+    value: Option<String>
+}
+
+impl Memos {
+    /// Gets the value memoized for `source` and `common_args`.
+    ///
+    /// The outer [`Option`] is [`None`] if no value was memoized.
+    pub fn get(&self, source: &StringSource, common_args: Option<&CommonCallArgs>) -> Option<Option<String>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).iter()
+            .find(|memo| memo.common_args.as_ref() == common_args && memo.source == *source)
+            .map(|memo| memo.value.clone())
+    }
+
+    /// Memoizes `value` for `source` and `common_args`.
+    pub fn insert(&self, source: &StringSource, common_args: Option<&CommonCallArgs>, value: Option<String>) {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).push(Memo {
+            source: source.clone(),
+            common_args: common_args.map(CommonCallArgs::to_owned_args),
+            value
+        });
+    }
+}
+
+impl Clone for Memos {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.0.lock().unwrap_or_else(PoisonError::into_inner).clone()))
+    }
+}
+
+impl PartialEq for Memos {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other) || *self.0.lock().unwrap_or_else(PoisonError::into_inner) == *other.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+impl Eq for Memos {}
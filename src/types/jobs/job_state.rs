@@ -1,5 +1,8 @@
 //! The state of a job as it's happening.
 
+#[cfg(feature = "http")]
+use std::borrow::Cow;
+
 use crate::types::*;
 use crate::util::*;
 use crate::glue::*;
@@ -23,7 +26,14 @@ pub struct JobState<'a> {
     pub commons: &'a Commons,
     /// The cache handler.
     #[cfg(feature = "cache")]
-    pub cache: &'a Cache
+    pub cache: &'a Cache,
+    /// The per-host HTTP rate limiter.
+    #[cfg(feature = "http")]
+    pub rate_limiter: &'a RateLimiter,
+    /// If [`Some`], each [`Rule`] applied by [`Rules::apply`] and [`Rules::apply_no_revert`] records a [`TraceEvent`] into it.
+    ///
+    /// If [`None`], nothing is recorded and the URL isn't cloned.
+    pub trace: Option<&'a mut Vec<TraceEvent>>
 }
 
 impl<'a> JobState<'a> {
@@ -40,14 +50,50 @@ impl<'a> JobState<'a> {
             params      : self.params,
             commons     : self.commons,
             #[cfg(feature = "cache")]
-            cache       : self.cache
+            cache       : self.cache,
+            #[cfg(feature = "http")]
+            rate_limiter: self.rate_limiter
         }
     }
 }
 
 /// Helper macro to make doctests less noisy.
 #[macro_export]
-#[cfg(feature = "cache")]
+#[cfg(all(feature = "cache", feature = "http"))]
+macro_rules! job_state {
+    ($job_state:ident; $(url = $url:expr;)? $(context = $context:expr;)? $(params = $params:expr;)? $(commons = $commons:expr;)? $(jobs_context = $jobs_context:expr;)?) => {
+        let url = "https://example.com";
+        $(let url = $url;)?
+        let mut scratchpad = Default::default();
+        let context: $crate::types::JobContext = Default::default();
+        $(let context = $context;)?
+        let jobs_context: $crate::types::JobsContext = Default::default();
+        $(let jobs_context = $jobs_context;)?
+        let params: $crate::types::Params = Default::default();
+        $(let params = $params;)?
+        let commons: $crate::types::Commons = Default::default();
+        $(let commons = $commons;)?
+        let cache = Default::default();
+        let rate_limiter = Default::default();
+        let mut url = BetterUrl::parse(url).unwrap();
+        let mut $job_state = url_cleaner::types::JobState {
+            url: &mut url,
+            scratchpad: &mut scratchpad,
+            common_args: None,
+            context: &context,
+            jobs_context: &jobs_context,
+            params: &params,
+            commons: &commons,
+            cache: &cache,
+            rate_limiter: &rate_limiter,
+            trace: None
+        };
+    };
+}
+
+/// Helper macro to make doctests less noisy.
+#[macro_export]
+#[cfg(all(feature = "cache", not(feature = "http")))]
 macro_rules! job_state {
     ($job_state:ident; $(url = $url:expr;)? $(context = $context:expr;)? $(params = $params:expr;)? $(commons = $commons:expr;)? $(jobs_context = $jobs_context:expr;)?) => {
         let url = "https://example.com";
@@ -71,14 +117,47 @@ macro_rules! job_state {
             jobs_context: &jobs_context,
             params: &params,
             commons: &commons,
-            cache: &cache
+            cache: &cache,
+            trace: None
+        };
+    };
+}
+
+/// Helper macro to make doctests less noisy.
+#[macro_export]
+#[cfg(all(not(feature = "cache"), feature = "http"))]
+macro_rules! job_state {
+    ($job_state:ident; $(url = $url:expr;)? $(context = $context:expr;)? $(params = $params:expr;)? $(commons = $commons:expr;)? $(jobs_context = $jobs_context:expr;)?) => {
+        let url = "https://example.com";
+        $(let url = $url;)?
+        let mut scratchpad = Default::default();
+        let context: $crate::types::JobContext = Default::default();
+        $(let context = $context;)?
+        let jobs_context: $crate::types::JobsContext = Default::default();
+        $(let jobs_context = $jobs_context;)?
+        let params: $crate::types::Params = Default::default();
+        $(let params = $params;)?
+        let commons: $crate::types::Commons = Default::default();
+        $(let commons = $commons;)?
+        let rate_limiter = Default::default();
+        let mut url = BetterUrl::parse(url).unwrap();
+        let mut $job_state = url_cleaner::types::JobState {
+            url: &mut url,
+            scratchpad: &mut scratchpad,
+            common_args: None,
+            context: &context,
+            jobs_context: &jobs_context,
+            params: &params,
+            commons: &commons,
+            rate_limiter: &rate_limiter,
+            trace: None
         };
     };
 }
 
 /// Helper macro to make doctests less noisy.
 #[macro_export]
-#[cfg(not(feature = "cache"))]
+#[cfg(all(not(feature = "cache"), not(feature = "http")))]
 macro_rules! job_state {
     ($job_state:ident; $(url = $url:expr;)? $(context = $context:expr;)? $(params = $params:expr;)? $(commons = $commons:expr;)? $(jobs_context = $jobs_context:expr;)?) => {
         let url = "https://example.com";
@@ -100,7 +179,8 @@ macro_rules! job_state {
             context: &context,
             jobs_context: &jobs_context,
             params: &params,
-            commons: &commons
+            commons: &commons,
+            trace: None
         };
     };
 }
@@ -144,25 +224,85 @@ pub struct JobStateView<'a> {
     /// 
     /// See [`JobState::cache`].
     #[cfg(feature = "cache")]
-    pub cache: &'a Cache
+    pub cache: &'a Cache,
+    /// The per-host HTTP rate limiter.
+    ///
+    /// See [`JobState::rate_limiter`].
+    #[cfg(feature = "http")]
+    pub rate_limiter: &'a RateLimiter
 }
 
 impl<'a> JobStateView<'a> {
-    /// Gets an HTTP client with [`Self`]'s configuration pre-applied.
-    /// # Errors
-    /// Errors if [`reqwest::ClientBuilder::build`] errors.
+    /// Gets the [`HttpClientConfig`] with [`Self`]'s [`CommonCallArgs::http_client_config_diff`] and `http_client_config_diff` applied.
+    ///
+    /// Used to make a client, its [`Self::dynamic_headers`], and to [`Self::wait_for_rate_limit`] without making the config multiple times.
     #[cfg(feature = "http")]
-    pub fn http_client(&self, http_client_config_diff: Option<&HttpClientConfigDiff>) -> reqwest::Result<reqwest::blocking::Client> {
-        debug!(Params::http_client, self, http_client_config_diff);
+    pub fn http_client_config(&self, http_client_config_diff: Option<&HttpClientConfigDiff>) -> Cow<'a, HttpClientConfig> {
         match http_client_config_diff {
             Some(http_client_config_diff) => {
                 let mut temp_http_client_config = self.params.http_client_config.clone();
                 if let Some(CommonCallArgs {http_client_config_diff: Some(x), ..}) = self.common_args {x.apply(&mut temp_http_client_config);}
                 http_client_config_diff.apply(&mut temp_http_client_config);
-                temp_http_client_config.apply(reqwest::blocking::ClientBuilder::new())
+                Cow::Owned(temp_http_client_config)
             },
-            None => {self.params.http_client_config.apply(reqwest::blocking::ClientBuilder::new())}
-        }?.build()
+            None => Cow::Borrowed(&self.params.http_client_config)
+        }
+    }
+
+    /// Gets an HTTP client with [`Self`]'s configuration pre-applied.
+    /// # Errors
+    /// Errors if [`reqwest::ClientBuilder::build`] errors.
+    #[cfg(feature = "http")]
+    pub fn http_client(&self, http_client_config_diff: Option<&HttpClientConfigDiff>) -> reqwest::Result<reqwest::blocking::Client> {
+        debug!(Params::http_client, self, http_client_config_diff);
+        self.http_client_config(http_client_config_diff).client()
+    }
+
+    /// Makes `http_client_config`'s [`HttpClientConfig::dynamic_headers`], to be sent with a request made with a client from [`HttpClientConfig::client`].
+    ///
+    /// Later headers with the same name overwrite earlier ones.
+    /// # Errors
+    /// If any call to [`DynamicHeader::make`] returns an error, that error is returned.
+    #[cfg(feature = "http")]
+    pub fn dynamic_headers(&self, http_client_config: &HttpClientConfig) -> Result<reqwest::header::HeaderMap, MakeDynamicHeaderError> {
+        let mut ret = reqwest::header::HeaderMap::new();
+        for header in &http_client_config.dynamic_headers {
+            if let Some((name, value)) = header.make(self)? {
+                ret.insert(name, value);
+            }
+        }
+        Ok(ret)
+    }
+
+    /// If `http_client_config`'s [`HttpClientConfig::rate_limit`] is [`Some`], blocks until [`Self::rate_limiter`] allows a request to `url`'s host.
+    ///
+    /// If `url` doesn't have a host, returns immediately.
+    #[cfg(feature = "http")]
+    pub fn wait_for_rate_limit(&self, url: &str, http_client_config: &HttpClientConfig) {
+        if let Some(rate_limit) = &http_client_config.rate_limit
+            && let Ok(url) = url::Url::parse(url)
+            && let Some(host) = url.host_str() {
+            self.rate_limiter.acquire(host, rate_limit);
+        }
+    }
+
+    /// [`Self::wait_for_rate_limit`] but waits asynchronously.
+    #[cfg(feature = "async")]
+    pub async fn wait_for_rate_limit_async(&self, url: &str, http_client_config: &HttpClientConfig) {
+        if let Some(rate_limit) = &http_client_config.rate_limit
+            && let Ok(url) = url::Url::parse(url)
+            && let Some(host) = url.host_str() {
+            self.rate_limiter.acquire_async(host, rate_limit).await;
+        }
+    }
+
+    /// [`Self::http_client`] but gets an async [`reqwest::Client`].
+    /// # Errors
+    /// Errors if [`reqwest::ClientBuilder::build`] errors.
+    #[cfg(feature = "async")]
+    pub fn async_http_client(&self, http_client_config_diff: Option<&HttpClientConfigDiff>) -> reqwest::Result<reqwest::Client> {
+        debug!(Params::async_http_client, self, http_client_config_diff);
+        self.http_client_config(http_client_config_diff).async_client()
     }
 
     /// Just returns itself.
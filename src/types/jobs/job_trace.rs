@@ -0,0 +1,94 @@
+//! Opt-in tracing of which [`Rule`]s did what, for debugging configs.
+
+use serde::{Serialize, Deserialize};
+
+use crate::types::*;
+
+/// A record of a single [`Rule`] being applied, as recorded into [`JobState::trace`].
+///
+/// Only the [`Rule`]s directly in the [`Rules`] applied with [`JobState::trace`] set record events, so [`Self::index`] always maps back to that [`Rules`], which for [`Job::do_traced`] is [`Config::rules`].
+///
+/// [`Rule`]s inside a [`Rule::Rules`], [`Rule::SharedCondition`], [`Rule::Repeat`], etc. don't record their own events. Their effects are part of the event of the [`Rule`] containing them.
+///
+/// Rules that return an error don't record an event.
+/// # Examples
+/// ```
+/// # use url_cleaner::types::*;
+/// url_cleaner::job_state!(job_state; url = "https://example.com/";);
+///
+/// let mut trace = Vec::new();
+/// job_state.trace = Some(&mut trace);
+///
+/// Rules(vec![
+///     Rule::Normal {condition: Condition::Never , mapper: Mapper::SetPart {part: UrlPart::Path, value: "/a".into()}},
+///     Rule::Normal {condition: Condition::Always, mapper: Mapper::SetPart {part: UrlPart::Path, value: "/b".into()}}
+/// ]).apply(&mut job_state).unwrap();
+///
+/// assert_eq!(trace.len(), 2);
+///
+/// assert_eq!(trace[0].index, 0);
+/// assert!(!trace[0].matched);
+/// assert!(!trace[0].changed());
+///
+/// assert_eq!(trace[1].index, 1);
+/// assert!(trace[1].matched);
+/// assert!(trace[1].changed());
+/// assert_eq!(trace[1].before.as_str(), "https://example.com/");
+/// assert_eq!(trace[1].after .as_str(), "https://example.com/b");
+///
+/// // Nested rules are part of the event of the rule containing them.
+/// url_cleaner::job_state!(job_state; url = "https://example.com/b";);
+///
+/// let mut trace = Vec::new();
+/// job_state.trace = Some(&mut trace);
+///
+/// Rules(vec![
+///     Rule::Repeat {
+///         rules: Rules(vec![Rule::Mapper(Mapper::RemovePathSuffix("/b".into()))]),
+///         limit: 10
+///     },
+///     Rule::Rules(Rules(vec![
+///         Rule::Normal {condition: Condition::Never , mapper: Mapper::SetPart {part: UrlPart::Path, value: "/c".into()}},
+///         Rule::Normal {condition: Condition::Always, mapper: Mapper::SetPart {part: UrlPart::Path, value: "/d".into()}}
+///     ]))
+/// ]).apply(&mut job_state).unwrap();
+///
+/// assert_eq!(trace.len(), 2);
+///
+/// assert_eq!(trace[0].index, 0);
+/// assert_eq!(trace[0].before.as_str(), "https://example.com/b");
+/// assert_eq!(trace[0].after .as_str(), "https://example.com/");
+///
+/// assert_eq!(trace[1].index, 1);
+/// assert_eq!(trace[1].before.as_str(), "https://example.com/");
+/// assert_eq!(trace[1].after .as_str(), "https://example.com/d");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceEvent {
+    /// The index of the [`Rule`] in the [`Rules`] containing it.
+    pub index: usize,
+    /// If the [`Rule`] is a [`Rule::Tagged`], its [`Rule::Tagged::id`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// If the [`Rule`] "matched".
+    ///
+    /// For [`Rule::Normal`] and [`Rule::SharedCondition`], this is if the condition passed.
+    ///
+    /// For [`Rule::Tagged`], this is if [`Params::rule_filter`] allowed it and the [`Rule`] inside it matched.
+    ///
+    /// For the map variants, this is if a value was found in the map.
+    ///
+    /// All other variants always match.
+    pub matched: bool,
+    /// The URL before the [`Rule`] was applied.
+    pub before: BetterUrl,
+    /// The URL after the [`Rule`] was applied.
+    pub after: BetterUrl
+}
+
+impl TraceEvent {
+    /// If [`Self::before`] and [`Self::after`] are different.
+    pub fn changed(&self) -> bool {
+        self.before != self.after
+    }
+}
@@ -5,6 +5,8 @@
 use std::borrow::Cow;
 
 use thiserror::Error;
+use strum::IntoStaticStr;
+use url::Url;
 
 use crate::types::*;
 use crate::glue::*;
@@ -21,6 +23,11 @@ pub struct JobsConfig<'a> {
     /// This is intentional so you can override it using, for example, command line arguments.
     #[cfg(feature = "cache")]
     pub cache: Cache,
+    /// The per-host rate limiter for HTTP requests.
+    ///
+    /// Shared by every [`Job`] made from [`Self`].
+    #[cfg(feature = "http")]
+    pub rate_limiter: RateLimiter
 }
 
 impl<'a> JobsConfig<'a> {
@@ -28,14 +35,16 @@ impl<'a> JobsConfig<'a> {
     /// 
     /// Can be more convenient than [`Jobs::iter`].
     #[allow(dead_code, reason = "Public API.")]
-    pub fn new_job(&'a self, job_config: JobConfig, jobs_context: &'a JobsContext) -> Job<'a> {
+    pub fn new_job<'b>(&'b self, job_config: JobConfig, jobs_context: &'b JobsContext) -> Job<'b> {
         Job {
             url: job_config.url,
             config: &self.config,
             context: job_config.context,
             jobs_context,
             #[cfg(feature = "cache")]
-            cache: &self.cache
+            cache: &self.cache,
+            #[cfg(feature = "http")]
+            rate_limiter: &self.rate_limiter
         }
     }
 }
@@ -74,12 +83,86 @@ impl<'a> Jobs<'a> {
                 Err(e) => Err(e.into())
             })
     }
+
+    /// Lazily makes and does each [`Job`], yielding the resulting [`Url`]s.
+    ///
+    /// Like [`Self::iter`] but fuses the making and doing of each job, so it's more convenient for embedding URL Cleaner in other tools.
+    /// # Examples
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use std::str::FromStr;
+    /// # use url_cleaner::types::*;
+    /// let config = Config {
+    ///     docs: Default::default(),
+    #[cfg_attr(feature = "cache", doc = "    cache_path: Default::default(),")]
+    ///     params: Default::default(),
+    ///     commons: Default::default(),
+    ///     canonicalize_output: None,
+    ///     rules: Rules(vec![Rule::Normal {condition: Condition::Always, mapper: Mapper::RemoveQuery}])
+    /// };
+    ///
+    /// let jobs = Jobs {
+    ///     jobs_config: JobsConfig {
+    ///         config: Cow::Borrowed(&config),
+    #[cfg_attr(feature = "cache", doc = "        cache: Default::default(),")]
+    #[cfg_attr(feature = "http", doc = "        rate_limiter: Default::default(),")]
+    ///     },
+    ///     context: Default::default(),
+    ///     job_configs_source: Box::new([
+    ///         JobConfig::from_str("https://example.com?a=1"),
+    ///         JobConfig::from_str("https://example.com/b?c=2"),
+    ///         JobConfig::from_str("not a url")
+    ///     ].into_iter())
+    /// };
+    ///
+    /// let mut cleaned = jobs.cleaned();
+    /// assert_eq!(cleaned.next().unwrap().unwrap().as_str(), "https://example.com/");
+    /// assert_eq!(cleaned.next().unwrap().unwrap().as_str(), "https://example.com/b");
+    /// assert!(matches!(cleaned.next().unwrap(), Err(JobError::MakeJobError(_))));
+    /// assert!(cleaned.next().is_none());
+    /// ```
+    pub fn cleaned(self) -> impl Iterator<Item = Result<Url, JobError>> + 'a {
+        let Self {jobs_config, context, job_configs_source} = self;
+        job_configs_source
+            .map(move |job_config_result| Ok(jobs_config.new_job(job_config_result.map_err(MakeJobError::from)?, &context).r#do()?))
+    }
 }
 
 /// The enum of errors that can happen when [`Jobs::iter`] tries to get a URL.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "MakeJobError::")]
 pub enum MakeJobError {
     /// Returned when a [`MakeJobConfigError`] is encountered.
     #[error(transparent)]
     MakeJobConfigError(#[from] MakeJobConfigError)
 }
+
+impl ErrorCode for MakeJobError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::MakeJobConfigError(e) => e.code(),
+        }
+    }
+}
+
+/// The enum of errors [`Jobs::cleaned`] can return.
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "JobError::")]
+pub enum JobError {
+    /// Returned when a [`MakeJobError`] is encountered.
+    #[error(transparent)]
+    MakeJobError(#[from] MakeJobError),
+    /// Returned when a [`DoJobError`] is encountered.
+    #[error(transparent)]
+    DoJobError(#[from] DoJobError)
+}
+
+impl ErrorCode for JobError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::MakeJobError(e) => e.code(),
+            Self::DoJobError(e) => e.code(),
+        }
+    }
+}
+
@@ -1,10 +1,10 @@
 //! The context of an entire [`Jobs`].
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use serde::{Serialize, Deserialize};
 
-#[expect(unused_imports, reason = "Used in a doc comment.")]
 use crate::types::*;
 use crate::util::*;
 
@@ -13,5 +13,10 @@ use crate::util::*;
 pub struct JobsContext {
     /// String variables.
     #[serde(default, skip_serializing_if = "is_default")]
-    pub vars: HashMap<String, String>
+    pub vars: HashMap<String, String>,
+    /// If [`Some`], every [`Condition`] and [`Mapper`] call is timed and recorded into it.
+    ///
+    /// Not serialized, as it's only meaningful within one process.
+    #[serde(skip)]
+    pub profile: Option<Arc<Profile>>
 }
@@ -0,0 +1,49 @@
+//! Provides [`OrdCmp`] which allows for comparing numbers.
+
+use serde::{Serialize, Deserialize};
+
+use crate::types::*;
+use crate::util::*;
+
+/// A comparison operator for things that implement [`Ord`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Suitability)]
+pub enum OrdCmp {
+    /// `<`.
+    Less,
+    /// `<=`.
+    LessOrEqual,
+    /// `==`.
+    Equal,
+    /// `!=`.
+    NotEqual,
+    /// `>=`.
+    GreaterOrEqual,
+    /// `>`.
+    Greater
+}
+
+impl OrdCmp {
+    /// Returns [`true`] if `left` compared to `right` with `self` is true.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// assert_eq!(OrdCmp::Less          .satisfied_by(1, 2), true );
+    /// assert_eq!(OrdCmp::Less          .satisfied_by(2, 2), false);
+    /// assert_eq!(OrdCmp::LessOrEqual   .satisfied_by(2, 2), true );
+    /// assert_eq!(OrdCmp::Equal         .satisfied_by(2, 2), true );
+    /// assert_eq!(OrdCmp::NotEqual      .satisfied_by(2, 2), false);
+    /// assert_eq!(OrdCmp::GreaterOrEqual.satisfied_by(2, 2), true );
+    /// assert_eq!(OrdCmp::Greater       .satisfied_by(2, 2), false);
+    /// assert_eq!(OrdCmp::Greater       .satisfied_by(3, 2), true );
+    /// ```
+    pub fn satisfied_by<T: Ord>(&self, left: T, right: T) -> bool {
+        match self {
+            Self::Less           => left <  right,
+            Self::LessOrEqual    => left <= right,
+            Self::Equal          => left == right,
+            Self::NotEqual       => left != right,
+            Self::GreaterOrEqual => left >= right,
+            Self::Greater        => left >  right
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Opt-in timing of [`Condition`]s and [`Mapper`]s, for finding what's making a config slow.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::sync::{Arc, Weak, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+#[expect(unused_imports, reason = "Used in a doc comment.")]
+use crate::types::*;
+
+/// The ID given to the next [`Profile`], used to find each thread's [`Shard`] for it.
+static NEXT_PROFILE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The entries recorded by one thread into one [`Profile`], keyed by kind and variant name.
+type Shard = Mutex<HashMap<(&'static str, &'static str), ProfileEntry>>;
+
+thread_local! {
+    /// The current thread's [`Shard`] for each [`Profile`] it has recorded into, keyed by [`Profile::id`].
+    static SHARDS: RefCell<Vec<(u64, Weak<Shard>)>> = const {RefCell::new(Vec::new())};
+}
+
+/// Thread-safe aggregation of how many times each [`Condition`] and [`Mapper`] variant was used and how long they took in total.
+///
+/// Set [`JobsContext::profile`] to have [`Condition::satisfied_by`] and [`Mapper::apply`] record into one.
+///
+/// Times are inclusive, so a [`Condition::All`]'s time includes the time of the [`Condition`]s inside it.
+///
+/// Each thread records into its own accumulator, so threads never wait on each other. [`Self::entries`] merges them.
+/// # Examples
+/// ```
+/// # use std::time::Duration;
+/// # use url_cleaner::types::*;
+/// let profile = Profile::default();
+///
+/// profile.record("Condition", "Always", Duration::from_millis(1));
+/// profile.record("Mapper"   , "None"  , Duration::from_millis(5));
+/// profile.record("Condition", "Always", Duration::from_millis(2));
+///
+/// assert_eq!(profile.entries(), vec![
+///     ("Mapper::None"     .to_string(), ProfileEntry {calls: 1, total: Duration::from_millis(5)}),
+///     ("Condition::Always".to_string(), ProfileEntry {calls: 2, total: Duration::from_millis(3)})
+/// ]);
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| for _ in 0..100 {profile.record("Mapper", "None", Duration::from_millis(1));});
+///     }
+/// });
+///
+/// assert_eq!(profile.entries()[0].1, ProfileEntry {calls: 401, total: Duration::from_millis(405)});
+/// ```
+#[derive(Debug)]
+pub struct Profile {
+    /// Identifies [`Self`] in [`SHARDS`].
+    id: u64,
+    /// Every thread's [`Shard`].
+    shards: Mutex<Vec<Arc<Shard>>>
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            id: NEXT_PROFILE_ID.fetch_add(1, Ordering::Relaxed),
+            shards: Default::default()
+        }
+    }
+}
+
+impl PartialEq for Profile {
+    /// Only [`true`] if both are the same [`Self`].
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+impl Eq for Profile {}
+
+/// The call count and cumulative time of a [`Profile`] entry.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileEntry {
+    /// The amount of times it was called.
+    pub calls: u64,
+    /// The total time spent in all calls.
+    pub total: Duration
+}
+
+impl ProfileEntry {
+    /// The average time spent per call.
+    ///
+    /// If [`Self::calls`] is zero, returns [`Duration::ZERO`].
+    pub fn mean(&self) -> Duration {
+        self.total.checked_div(u32::try_from(self.calls).unwrap_or(u32::MAX)).unwrap_or_default()
+    }
+}
+
+/// Locks `mutex`.
+///
+/// If `mutex` is poisoned, the poison is cleared, as a panic while recording can't leave the counts in an invalid state.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| {
+        mutex.clear_poison();
+        e.into_inner()
+    })
+}
+
+impl Profile {
+    /// Gets the current thread's [`Shard`], making it if this is the thread's first time recording into [`Self`].
+    fn shard(&self) -> Arc<Shard> {
+        SHARDS.with_borrow_mut(|shards| {
+            if let Some(shard) = shards.iter().find(|(id, _)| *id == self.id).and_then(|(_, shard)| shard.upgrade()) {
+                return shard;
+            }
+            shards.retain(|(_, shard)| shard.strong_count() > 0);
+            let shard = Arc::<Shard>::default();
+            lock(&self.shards).push(shard.clone());
+            shards.push((self.id, Arc::downgrade(&shard)));
+            shard
+        })
+    }
+
+    /// Adds a call taking `elapsed` to the entry for `kind`'s `variant`.
+    pub fn record(&self, kind: &'static str, variant: &'static str, elapsed: Duration) {
+        let shard = self.shard();
+        let mut entries = lock(&shard);
+        let entry = entries.entry((kind, variant)).or_default();
+        entry.calls = entry.calls.saturating_add(1);
+        entry.total = entry.total.saturating_add(elapsed);
+    }
+
+    /// Times `f` and records it under `kind`'s `variant`.
+    pub(crate) fn time<T>(&self, kind: &'static str, variant: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let ret = f();
+        self.record(kind, variant, start.elapsed());
+        ret
+    }
+
+    /// Gets all entries merged from every thread, sorted by total time descending, then by name.
+    ///
+    /// Names are in the form `Kind::Variant`.
+    pub fn entries(&self) -> Vec<(String, ProfileEntry)> {
+        let mut merged = HashMap::<(&'static str, &'static str), ProfileEntry>::new();
+        for shard in lock(&self.shards).iter() {
+            for (key, entry) in lock(shard).iter() {
+                let merged_entry = merged.entry(*key).or_default();
+                merged_entry.calls = merged_entry.calls.saturating_add(entry.calls);
+                merged_entry.total = merged_entry.total.saturating_add(entry.total);
+            }
+        }
+        let mut ret = merged.into_iter().map(|((kind, variant), entry)| (format!("{kind}::{variant}"), entry)).collect::<Vec<_>>();
+        ret.sort_by(|(name1, entry1), (name2, entry2)| entry2.total.cmp(&entry1.total).then_with(|| name1.cmp(name2)));
+        ret
+    }
+}
+
+impl Display for Profile {
+    /// A tab separated table with a header line and one line per entry in the order of [`Self::entries`].
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "Total\tCalls\tMean\tName")?;
+        for (name, entry) in self.entries() {
+            writeln!(formatter, "{:?}\t{}\t{:?}\t{name}", entry.total, entry.calls, entry.mean())?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 //! The part of a config that actually modified URLs.
 
 use std::ops::{Deref, DerefMut};
+use std::collections::HashSet;
 
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use strum::IntoStaticStr;
 
 mod conditions;
 pub use conditions::*;
@@ -146,6 +148,36 @@ pub enum Rule {
     /// # Errors
     /// If the call to [`Rules::apply`] returns an error, that error is returned.
     Rules(Rules),
+    /// Applies the first contained [`Self`] that "matches", then stops.
+    ///
+    /// [`Self::Normal`] and [`Self::SharedCondition`] match if their condition passes, and [`Self::PartMap`], [`Self::StringMap`], etc. match if their map has a value for the part/string.
+    /// Other variants (including [`Self::IfElse`] and [`Self::Mapper`]) always match, so they act like a default case when put last.
+    ///
+    /// Useful for dispatch-style rules where at most one should apply, and faster than checking every condition.
+    /// # Errors
+    /// If any call to [`Self::apply`] returns an error, that error is returned and `job_state.url` and `job_state.scratchpad` are left unmodified.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// url_cleaner::job_state!(job_state; url = "https://example.com/";);
+    ///
+    /// let rule = Rule::FirstMatch(Rules(vec![
+    ///     Rule::Normal {condition: Condition::HostIs(Some("other.com".into())), mapper: Mapper::SetPart {part: UrlPart::Path, value: "/other".into()}},
+    ///     Rule::Normal {condition: Condition::UrlHasHost                      , mapper: Mapper::SetPart {part: UrlPart::Path, value: "/first".into()}},
+    ///     Rule::Normal {condition: Condition::Always                          , mapper: Mapper::SetPart {part: UrlPart::Path, value: "/second".into()}}
+    /// ]));
+    ///
+    /// rule.apply(&mut job_state).unwrap();
+    /// assert_eq!(job_state.url.as_str(), "https://example.com/first");
+    ///
+    /// // Errors revert the changes made by earlier rules.
+    /// Rule::FirstMatch(Rules(vec![
+    ///     Rule::Normal {condition: Condition::Never , mapper: Mapper::None},
+    ///     Rule::Normal {condition: Condition::Always, mapper: Mapper::Error}
+    /// ])).apply(&mut job_state).unwrap_err();
+    /// assert_eq!(job_state.url.as_str(), "https://example.com/first");
+    /// ```
+    FirstMatch(Rules),
     /// If the call to [`Condition::satisfied_by`] returns `Ok(true)`, calls [`Self::IfElse::mapper`]'s [`Mapper::apply`] on the provided URL, otherwise use [`Self::IfElse::else_mapper`].
     /// # Errors
     /// If the call to [`Condition::satisfied_by`] returns an error, that error is returned.
@@ -172,6 +204,46 @@ pub enum Rule {
     #[cfg(feature = "custom")]
     #[suitable(never)]
     Custom(FnWrapper<fn(&mut JobState) -> Result<(), RuleError>>),
+    /// Gives `rule` an ID and tags, then applies it if [`Params::rule_filter`] allows its tags.
+    ///
+    /// If [`Params::rule_filter`] doesn't allow [`Self::Tagged::tags`], `rule` is skipped.
+    ///
+    /// If [`Self::Tagged::tags`] is empty, `rule` is always applied, the same as an untagged [`Self`].
+    ///
+    /// If [`JobState::trace`] is [`Some`], [`Self::Tagged::id`] is included in the [`TraceEvent`].
+    /// # Errors
+    /// If the call to [`Self::apply`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// url_cleaner::job_state!(job_state; url = "https://example.com/";);
+    ///
+    /// let mut trace = Vec::new();
+    /// job_state.trace = Some(&mut trace);
+    ///
+    /// Rules(vec![Rule::Tagged {
+    ///     id: Some("set_path".into()),
+    ///     tags: ["path".into()].into(),
+    ///     rule: Box::new(Rule::Mapper(Mapper::SetPart {part: UrlPart::Path, value: "/a".into()}))
+    /// }]).apply(&mut job_state).unwrap();
+    ///
+    /// assert_eq!(trace[0].id.as_deref(), Some("set_path"));
+    /// assert_eq!(trace[0].after.as_str(), "https://example.com/a");
+    /// ```
+    Tagged {
+        /// An optional name for the rule, included in [`TraceEvent::id`].
+        ///
+        /// Defaults to [`None`].
+        #[serde(default, skip_serializing_if = "is_default")]
+        id: Option<String>,
+        /// Tags used by [`RuleFilter`] to decide whether or not to run the rule.
+        ///
+        /// Defaults to an empty [`HashSet`].
+        #[serde(default, skip_serializing_if = "is_default")]
+        tags: HashSet<String>,
+        /// The [`Self`] to apply.
+        rule: Box<Self>
+    },
     /// The most basic type of rule. If the call to [`Condition::satisfied_by`] returns `Ok(true)`, calls [`Mapper::apply`] on the provided URL.
     /// 
     /// This is the last variant because of the [`#[serde(untageed)]`](https://serde.rs/variant-attrs.html#untagged) macro.
@@ -198,11 +270,85 @@ pub enum Rule {
     }
 }
 
+/// Decides which [`Rule::Tagged`]s to run based on their [`Rule::Tagged::tags`].
+///
+/// Set with [`Params::rule_filter`], and from the CLI with `--only-tags` and `--skip-tags`.
+///
+/// Untagged [`Rule`]s, meaning other [`Rule`] variants and [`Rule::Tagged`]s with no [`Rule::Tagged::tags`], are always run, even if [`Self::only_tags`] is set, unless they're inside a [`Rule::Tagged`] that isn't.
+/// # Examples
+/// ```
+/// # use std::collections::HashSet;
+/// # use url_cleaner::types::*;
+/// let tags = |tags: &[&str]| tags.iter().map(|tag| tag.to_string()).collect::<HashSet<_>>();
+///
+/// let filter = RuleFilter::default();
+/// assert!(filter.allows(&tags(&[])));
+/// assert!(filter.allows(&tags(&["http"])));
+///
+/// let filter = RuleFilter {only_tags: tags(&["tracking"]), skip_tags: tags(&["http"])};
+/// assert!( filter.allows(&tags(&["tracking"])));
+/// assert!(!filter.allows(&tags(&["tracking", "http"])));
+/// assert!(!filter.allows(&tags(&["http"])));
+/// assert!(!filter.allows(&tags(&["other"])));
+/// assert!( filter.allows(&tags(&[])));
+///
+/// // Tag-based exclusion in a config.
+/// let config: Config = serde_json::from_value(serde_json::json!({
+///     "params": {"rule_filter": {"skip_tags": ["http"]}},
+///     "rules": [
+///         {"Tagged": {"id": "expand", "tags": ["http"], "rule": {"condition": "Always", "mapper": {"SetPart": {"part": "Path", "value": "/expanded"}}}}},
+///         {"Tagged": {"tags": ["tracking"], "rule": {"condition": "Always", "mapper": {"RemoveQueryParams": ["utm_source"]}}}}
+///     ]
+/// })).unwrap();
+///
+/// url_cleaner::job_state!(job_state; url = "https://example.com/a?utm_source=x"; params = config.params.clone(););
+/// config.rules.apply(&mut job_state).unwrap();
+/// assert_eq!(job_state.url.as_str(), "https://example.com/a");
+///
+/// // Untagged rules always run, whether or not they're in a `Tagged`.
+/// let config: Config = serde_json::from_value(serde_json::json!({
+///     "params": {"rule_filter": {"only_tags": ["tracking"]}},
+///     "rules": [
+///         {"Tagged": {"tags": ["http"], "rule": {"condition": "Always", "mapper": {"SetPart": {"part": "Path", "value": "/expanded"}}}}},
+///         {"Tagged": {"id": "fragment", "rule": {"condition": "Always", "mapper": {"SetPart": {"part": "Fragment", "value": null}}}}},
+///         {"condition": "Always", "mapper": {"SetPart": {"part": "Username", "value": ""}}},
+///         {"Tagged": {"tags": ["tracking"], "rule": {"condition": "Always", "mapper": {"RemoveQueryParams": ["utm_source"]}}}}
+///     ]
+/// })).unwrap();
+///
+/// url_cleaner::job_state!(job_state; url = "https://user@example.com/a?utm_source=x#b"; params = config.params.clone(););
+/// config.rules.apply(&mut job_state).unwrap();
+/// assert_eq!(job_state.url.as_str(), "https://example.com/a");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Suitability)]
+pub struct RuleFilter {
+    /// If not empty, only [`Rule::Tagged`]s with at least one of these tags, and untagged [`Rule`]s, are run.
+    ///
+    /// Defaults to an empty [`HashSet`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub only_tags: HashSet<String>,
+    /// [`Rule::Tagged`]s with any of these tags aren't run, even if they're allowed by [`Self::only_tags`].
+    ///
+    /// Defaults to an empty [`HashSet`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub skip_tags: HashSet<String>
+}
+
+impl RuleFilter {
+    /// Returns [`true`] if a [`Rule::Tagged`] with `tags` should be run.
+    ///
+    /// If `tags` is empty, always returns [`true`], the same as for untagged [`Rule`]s.
+    pub fn allows(&self, tags: &HashSet<String>) -> bool {
+        tags.is_empty() || ((self.only_tags.is_empty() || !self.only_tags.is_disjoint(tags)) && self.skip_tags.is_disjoint(tags))
+    }
+}
+
 /// Serde helper function. The default value of [`Rule::Repeat::limit`].
 const fn get_10_u64() -> u64 {10}
 
 /// The errors that [`Rule`] can return.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "RuleError::")]
 pub enum RuleError {
     /// The condition returned an error.
     #[error(transparent)]
@@ -228,6 +374,18 @@ pub enum RuleError {
     Custom(Box<dyn std::error::Error + Send>)
 }
 
+impl ErrorCode for RuleError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::ConditionError(e) => e.code(),
+            Self::MapperError(e) => e.code(),
+            Self::StringSourceError(e) => e.code(),
+            Self::CommonCallArgsError(e) => e.code(),
+            _ => self.into()
+        }
+    }
+}
+
 impl Rule {
     /// Apply the rule to the url in-place.
     /// # Errors
@@ -236,16 +394,31 @@ impl Rule {
     /// If an error occurs, `job_state` is effectively unmodified, though the mutable parts may be clones.
     pub fn apply(&self, job_state: &mut JobState) -> Result<(), RuleError> {
         debug!(Rule::apply, self, job_state);
+        self.apply_and_report(job_state).map(|_| ())
+    }
+
+    /// [`Self::apply`] but returns [`true`] if `self` "matched".
+    ///
+    /// [`Self::Normal`] and [`Self::SharedCondition`] match if their condition passes. Map variants match if a value is found.
+    ///
+    /// All other variants always match.
+    ///
+    /// Used by [`Self::FirstMatch`].
+    /// # Errors
+    /// See each of [`Self`]'s variant's documentation for details.
+    fn apply_and_report(&self, job_state: &mut JobState) -> Result<bool, RuleError> {
         Ok(match self {
             Self::Normal{condition, mapper} => if condition.satisfied_by(&job_state.to_view())? {
                 mapper.apply(job_state)?;
-            },
-            Self::PartMap        {part , map} => if let Some(x) = map.get(part .get( job_state.url      ) ) {x.apply(job_state)?;},
-            Self::PartRuleMap    {part , map} => if let Some(x) = map.get(part .get( job_state.url      ) ) {x.apply(job_state)?;},
-            Self::PartRulesMap   {part , map} => if let Some(x) = map.get(part .get( job_state.url      ) ) {x.apply(job_state)?;},
-            Self::StringMap      {value, map} => if let Some(x) = map.get(value.get(&job_state.to_view())?) {x.apply(job_state)?;},
-            Self::StringRuleMap  {value, map} => if let Some(x) = map.get(value.get(&job_state.to_view())?) {x.apply(job_state)?;},
-            Self::StringRulesMap {value, map} => if let Some(x) = map.get(value.get(&job_state.to_view())?) {x.apply(job_state)?;},
+                true
+            } else {false},
+            Self::Tagged {tags, rule, ..} => job_state.params.rule_filter.allows(tags) && rule.apply_and_report(job_state)?,
+            Self::PartMap        {part , map} => if let Some(x) = map.get(part .get( job_state.url      ) ) {x.apply(job_state)?; true} else {false},
+            Self::PartRuleMap    {part , map} => if let Some(x) = map.get(part .get( job_state.url      ) ) {x.apply(job_state)?; true} else {false},
+            Self::PartRulesMap   {part , map} => if let Some(x) = map.get(part .get( job_state.url      ) ) {x.apply(job_state)?; true} else {false},
+            Self::StringMap      {value, map} => if let Some(x) = map.get(value.get(&job_state.to_view())?) {x.apply(job_state)?; true} else {false},
+            Self::StringRuleMap  {value, map} => if let Some(x) = map.get(value.get(&job_state.to_view())?) {x.apply(job_state)?; true} else {false},
+            Self::StringRulesMap {value, map} => if let Some(x) = map.get(value.get(&job_state.to_view())?) {x.apply(job_state)?; true} else {false},
             Self::Repeat{rules, limit} => {
                 let original_url = job_state.url.clone();
                 let original_scratchpad = job_state.scratchpad.clone();
@@ -256,26 +429,48 @@ impl Rule {
                     previous_scratchpad = job_state.scratchpad.clone();
                     match rules.apply_no_revert(job_state) {
                         Ok(()) => if job_state.url == &previous_url && job_state.scratchpad == &previous_scratchpad {break;},
-                        e @ Err(_) => {
+                        Err(e) => {
                             *job_state.url = original_url;
                             *job_state.scratchpad = original_scratchpad;
-                            return e;
+                            return Err(e);
                         }
                     }
                 }
+                true
             },
             Self::SharedCondition{condition, rules} => if condition.satisfied_by(&job_state.to_view())? {
-                rules.apply(job_state)?
+                rules.apply(job_state)?;
+                true
+            } else {false},
+            Self::Rules(rules) => {rules.apply(job_state)?; true},
+            Self::FirstMatch(rules) => {
+                let old_url = job_state.url.clone();
+                let old_scratchpad = job_state.scratchpad.clone();
+                let mut matched = false;
+                for rule in rules.iter() {
+                    match rule.apply_and_report(job_state) {
+                        Ok(false) => {},
+                        Ok(true) => {matched = true; break;},
+                        Err(e) => {
+                            *job_state.url = old_url;
+                            *job_state.scratchpad = old_scratchpad;
+                            return Err(e);
+                        }
+                    }
+                }
+                matched
             },
-            Self::Rules(rules) => rules.apply(job_state)?,
-            Self::IfElse {condition, mapper, else_mapper} => if condition.satisfied_by(&job_state.to_view())? {
-                mapper.apply(job_state)?;
-            } else {
-                else_mapper.apply(job_state)?;
+            Self::IfElse {condition, mapper, else_mapper} => {
+                if condition.satisfied_by(&job_state.to_view())? {
+                    mapper.apply(job_state)?;
+                } else {
+                    else_mapper.apply(job_state)?;
+                }
+                true
             },
-            Self::Mapper(mapper) => mapper.apply(job_state)?,
+            Self::Mapper(mapper) => {mapper.apply(job_state)?; true},
             Self::Common(common_call) => {
-                job_state.commons.rules.get(get_str!(common_call.name, job_state, RuleError)).ok_or(RuleError::CommonRuleNotFound)?.apply(&mut JobState {
+                job_state.commons.rules.get(get_str!(common_call.name, job_state, RuleError)).ok_or(RuleError::CommonRuleNotFound)?.apply_and_report(&mut JobState {
                     common_args: Some(&common_call.args.make(&job_state.to_view())?),
                     url: job_state.url,
                     context: job_state.context,
@@ -283,12 +478,15 @@ impl Rule {
                     scratchpad: job_state.scratchpad,
                     #[cfg(feature = "cache")]
                     cache: job_state.cache,
+                    #[cfg(feature = "http")]
+                    rate_limiter: job_state.rate_limiter,
                     commons: job_state.commons,
-                    jobs_context: job_state.jobs_context
+                    jobs_context: job_state.jobs_context,
+                    trace: job_state.trace.as_deref_mut()
                 })?
             },
             #[cfg(feature = "custom")]
-            Self::Custom(function) => function(job_state)?
+            Self::Custom(function) => {function(job_state)?; true}
         })
     }
 }
@@ -341,12 +539,29 @@ impl Rules {
     /// If an error is returned, `job_state.url` and `job_state.scratchpad` are not reverted.
     ///
     /// This is fine if you guarantee discarding the URL on an error, such as [`Job::do`], but can result in unpredictable and undefined outputs.
+    ///
+    /// If [`JobState::trace`] is [`Some`], a [`TraceEvent`] is recorded for each [`Rule`] that doesn't return an error.
+    /// [`JobState::trace`] is [`None`] while each [`Rule`] is applied, so [`Rule`]s nested inside them don't record events.
     /// # Errors
     /// If any contained [`Rule`] returns an error, that error is returned.
     pub fn apply_no_revert(&self, job_state: &mut JobState) -> Result<(), RuleError> {
         debug!(Rules::apply_no_revert, self, job_state);
-        for rule in &self.0 {
-            rule.apply(job_state)?;
+        for (index, rule) in self.0.iter().enumerate() {
+            if let Some(trace) = job_state.trace.take() {
+                let before = job_state.url.clone();
+                let matched = rule.apply_and_report(job_state);
+                job_state.trace = Some(trace);
+                let matched = matched?;
+                if let Some(trace) = job_state.trace.as_deref_mut() {
+                    let id = match rule {
+                        Rule::Tagged {id, ..} => id.clone(),
+                        _ => None
+                    };
+                    trace.push(TraceEvent {index, id, matched, before, after: job_state.url.clone()});
+                }
+            } else {
+                rule.apply(job_state)?;
+            }
         }
         Ok(())
     }
@@ -2,6 +2,7 @@
 
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use strum::IntoStaticStr;
 
 use crate::types::*;
 use crate::util::*;
@@ -154,6 +155,19 @@ pub enum StringLocation {
     /// StringLocation::Before(7).satisfied_by("abcdef", "a"  ).unwrap_err();
     /// ```
     Before(isize),
+    /// Checks if at least the specified amount of non-overlapping instances of the needle exist in the haystack.
+    ///
+    /// Useful for detecting things that got doubled up.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::StringLocation;
+    /// assert_eq!(StringLocation::MinOccurrences(0).satisfied_by("ababab", "ab").unwrap(), true );
+    /// assert_eq!(StringLocation::MinOccurrences(2).satisfied_by("ababab", "ab").unwrap(), true );
+    /// assert_eq!(StringLocation::MinOccurrences(3).satisfied_by("ababab", "ab").unwrap(), true );
+    /// assert_eq!(StringLocation::MinOccurrences(4).satisfied_by("ababab", "ab").unwrap(), false);
+    /// assert_eq!(StringLocation::MinOccurrences(2).satisfied_by("aaa"   , "aa").unwrap(), false);
+    /// ```
+    MinOccurrences(usize),
     /// Checks equality.
     /// 
     /// Meant primarily for use with [`Self::AnySegment`] and [`Self::NthSegment`].
@@ -219,7 +233,8 @@ impl Default for StringLocation {
 
 /// The enum of all possible errors [`StringLocation::satisfied_by`] can return.
 #[allow(clippy::enum_variant_names, reason = "I disagree.")]
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "StringLocationError::")]
 pub enum StringLocationError {
     /// Returned when [`StringLocation::Error`] is used.
     #[error("StringLocation::Error was used.")]
@@ -243,6 +258,8 @@ pub enum StringLocationError {
     }
 }
 
+impl ErrorCode for StringLocationError {}
+
 impl StringLocation {
     /// Checks if `needle` exists in `haystack` according to `self`'s rules.
     /// # Errors
@@ -311,6 +328,11 @@ impl StringLocation {
                 needle
             )?,
 
+            Self::MinOccurrences(n)    => match n.checked_sub(1) {
+                Some(n) => haystack.matches(needle).nth(n).is_some(),
+                None => true
+            },
+
             Self::Equals               => haystack==needle,
             Self::AnySegment {split, location} => {
                 for segment in haystack.split(split) {
@@ -6,6 +6,7 @@ use std::collections::HashMap;
 
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use strum::IntoStaticStr;
 
 use crate::types::*;
 use crate::glue::*;
@@ -102,10 +103,56 @@ pub enum StringMatcher {
         #[serde(default)]
         r#where: StringLocation
     },
+    /// Passes if the string contains any of the specified substrings.
+    ///
+    /// If the list is empty, fails.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringMatcher::ContainsAnyOf(vec!["utm_".into(), "fbclid".into()]).satisfied_by("a=1&utm_source=2", &job_state.to_view()).unwrap(), true );
+    /// assert_eq!(StringMatcher::ContainsAnyOf(vec!["utm_".into(), "fbclid".into()]).satisfied_by("utm_a=1&fbclid=2", &job_state.to_view()).unwrap(), true );
+    /// assert_eq!(StringMatcher::ContainsAnyOf(vec!["utm_".into(), "fbclid".into()]).satisfied_by("a=1&b=2"         , &job_state.to_view()).unwrap(), false);
+    /// assert_eq!(StringMatcher::ContainsAnyOf(vec![]                             ).satisfied_by("a=1&b=2"         , &job_state.to_view()).unwrap(), false);
+    /// ```
+    ContainsAnyOf(Vec<String>),
+    /// Passes if the string contains all of the specified substrings.
+    ///
+    /// If the list is empty, passes.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringMatcher::ContainsAllOf(vec!["utm_".into(), "fbclid".into()]).satisfied_by("utm_a=1&fbclid=2", &job_state.to_view()).unwrap(), true );
+    /// assert_eq!(StringMatcher::ContainsAllOf(vec!["utm_".into(), "fbclid".into()]).satisfied_by("a=1&utm_source=2", &job_state.to_view()).unwrap(), false);
+    /// assert_eq!(StringMatcher::ContainsAllOf(vec!["utm_".into(), "fbclid".into()]).satisfied_by("a=1&b=2"         , &job_state.to_view()).unwrap(), false);
+    /// assert_eq!(StringMatcher::ContainsAllOf(vec![]                             ).satisfied_by("a=1&b=2"         , &job_state.to_view()).unwrap(), true );
+    /// ```
+    ContainsAllOf(Vec<String>),
     /// Passes if the string equals the specified value.
     Equals(StringSource),
     /// Passes if the provided string is contained in the specified [`HashSet`].
     IsOneOf(HashSet<String>),
+    /// Passes if the provided string is not contained in the specified [`HashSet`].
+    ///
+    /// Equivalent to a [`Self::Not`] of a [`Self::IsOneOf`], but reads better in configs.
+    ///
+    /// Can also be written as `NotInHashSet`.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringMatcher::NotOneOf(["a".to_string(), "b".to_string()].into()).satisfied_by("a", &job_state.to_view()).unwrap(), false);
+    /// assert_eq!(StringMatcher::NotOneOf(["a".to_string(), "b".to_string()].into()).satisfied_by("c", &job_state.to_view()).unwrap(), true );
+    ///
+    /// let matcher: StringMatcher = serde_json::from_str(r#"{"NotInHashSet": ["a"]}"#).unwrap();
+    /// assert_eq!(matcher, StringMatcher::NotOneOf(["a".to_string()].into()));
+    /// ```
+    #[serde(alias = "NotInHashSet")]
+    NotOneOf(HashSet<String>),
     /// Passes if the string is in the specified [`Params::sets`] set.
     /// 
     /// See also: [`Self::IsOneOf`].
@@ -148,6 +195,40 @@ pub enum StringMatcher {
     /// # Errors
     /// If any call to [`CharMatcher::satisfied_by`] return an error, that error is returned.
     AnyCharMatches(CharMatcher),
+    /// If `all` is `true`, passes if all of the haystack's [`char`]s are in `allowed`.
+    ///
+    /// If `all` is `false`, passes if any of the haystack's [`char`]s are in `allowed`.
+    ///
+    /// Like [`Self::OnlyTheseChars`] but easier to write in configs.
+    ///
+    /// An empty haystack passes when `all` is `true` and fails when `all` is `false`.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// let token = StringMatcher::CharSet {allowed: "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_-".into(), all: true};
+    /// assert_eq!(token.satisfied_by("abc_DEF-123", &job_state.to_view()).unwrap(), true );
+    /// assert_eq!(token.satisfied_by("abc def"    , &job_state.to_view()).unwrap(), false);
+    /// assert_eq!(token.satisfied_by(""           , &job_state.to_view()).unwrap(), true );
+    ///
+    /// let has_space = StringMatcher::CharSet {allowed: " \t".into(), all: false};
+    /// assert_eq!(has_space.satisfied_by("abc def", &job_state.to_view()).unwrap(), true );
+    /// assert_eq!(has_space.satisfied_by("abcdef" , &job_state.to_view()).unwrap(), false);
+    /// assert_eq!(has_space.satisfied_by(""       , &job_state.to_view()).unwrap(), false);
+    ///
+    /// let matcher: StringMatcher = serde_json::from_str(r#"{"CharSet": {"allowed": "abc"}}"#).unwrap();
+    /// assert_eq!(matcher, StringMatcher::CharSet {allowed: "abc".into(), all: true});
+    /// ```
+    CharSet {
+        /// The [`char`]s to look for.
+        allowed: String,
+        /// If `true`, all of the haystack's [`char`]s have to be in [`Self::CharSet::allowed`]. If `false`, only one has to be.
+        ///
+        /// Defaults to `true`.
+        #[serde(default = "get_true", skip_serializing_if = "is_true")]
+        all: bool
+    },
     /// [`str::is_ascii`].
     IsAscii,
     /// Passes if the `n`th segment of the string passes specified matcher.
@@ -241,6 +322,37 @@ pub enum StringMatcher {
         /// The string of segments to search for in the haystack.
         value: Box<StringSource>
     },
+    /// Ignores the haystack and instead checks the specified part of the job's URL with `matcher`.
+    ///
+    /// Allows a single [`Self`] to check multiple parts of the URL.
+    /// # Errors
+    /// If the part is [`None`], returns the error [`StringMatcherError::PartIsNone`].
+    ///
+    /// If the call to [`Self::satisfied_by`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// url_cleaner::job_state!(job_state; url = "https://example.com/abc";);
+    ///
+    /// let matcher = StringMatcher::All(vec![
+    ///     StringMatcher::Equals("/abc".into()),
+    ///     StringMatcher::UrlPart {part: UrlPart::Domain, matcher: Box::new(StringMatcher::Equals("example.com".into()))}
+    /// ]);
+    ///
+    /// assert_eq!(matcher.satisfied_by(&job_state.url.path().to_string(), &job_state.to_view()).unwrap(), true );
+    /// assert_eq!(matcher.satisfied_by("/def"                            , &job_state.to_view()).unwrap(), false);
+    ///
+    /// *job_state.url = BetterUrl::parse("https://example.org/abc").unwrap();
+    /// assert_eq!(matcher.satisfied_by(&job_state.url.path().to_string(), &job_state.to_view()).unwrap(), false);
+    ///
+    /// StringMatcher::UrlPart {part: UrlPart::Fragment, matcher: Box::new(StringMatcher::Always)}.satisfied_by("", &job_state.to_view()).unwrap_err();
+    /// ```
+    UrlPart {
+        /// The part of the URL to use as the haystack.
+        part: UrlPart,
+        /// The [`Self`] to check the part with.
+        matcher: Box<Self>
+    },
     /// Uses a [`Self`] from the [`JobState::commons`]'s [`Commons::string_matchers`].
     Common(CommonCall),
     /// Uses a function pointer.
@@ -268,7 +380,8 @@ impl From<GlobWrapper> for StringMatcher {
 
 /// The enum of all possible errors [`StringMatcher::satisfied_by`] can return.
 #[allow(clippy::enum_variant_names, reason = "I disagree.")]
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "StringMatcherError::")]
 pub enum StringMatcherError {
     /// Returned when [`StringMatcher::Error`] is used.
     #[error("StringMatcher::Error was used.")]
@@ -297,6 +410,9 @@ pub enum StringMatcherError {
         /// The error returned by [`StringMatcher::TryElse::else`],
         else_error: Box<Self>
     },
+    /// Returned when a [`StringMatcher::UrlPart`]'s part is [`None`].
+    #[error("The requested part of the URL was None.")]
+    PartIsNone,
     /// Returned when the requested segment is not found.
     #[error("The requested segment was not found.")]
     SegmentNotFound,
@@ -318,6 +434,19 @@ pub enum StringMatcherError {
     Custom(Box<dyn std::error::Error + Send>)
 }
 
+impl ErrorCode for StringMatcherError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::StringLocationError(e) => e.code(),
+            Self::StringModificationError(e) => e.code(),
+            Self::StringSourceError(e) => e.code(),
+            Self::CharMatcherError(e) => e.code(),
+            Self::CommonCallArgsError(e) => e.code(),
+            _ => self.into()
+        }
+    }
+}
+
 impl StringMatcher {
     /// # Errors
     /// See each of [`Self`]'s variant's documentation for details.
@@ -371,7 +500,10 @@ impl StringMatcher {
             // Other.
 
             Self::IsOneOf(hash_set) => hash_set.contains(haystack),
+            Self::NotOneOf(hash_set) => !hash_set.contains(haystack),
             Self::Contains {r#where, value} => r#where.satisfied_by(haystack, get_str!(value, job_state, StringMatcherError))?,
+            Self::ContainsAnyOf(needles) => needles.iter().any(|needle| haystack.contains(&**needle)),
+            Self::ContainsAllOf(needles) => needles.iter().all(|needle| haystack.contains(&**needle)),
             Self::Modified {modification, matcher} => matcher.satisfied_by(&{let mut temp=haystack.to_string(); modification.apply(&mut temp, job_state)?; temp}, job_state)?,
             #[cfg(feature = "regex")] Self::Regex(regex) => regex.get_regex()?.is_match(haystack),
             #[cfg(feature = "glob" )] Self::Glob(glob) => glob.matches(haystack),
@@ -392,6 +524,8 @@ impl StringMatcher {
                 }
                 false
             },
+            Self::CharSet {allowed, all: true } => haystack.chars().all(|c| allowed.contains(c)),
+            Self::CharSet {allowed, all: false} => haystack.chars().any(|c| allowed.contains(c)),
             Self::IsAscii => haystack.is_ascii(),
             Self::NthSegmentMatches {n, split, matcher} => matcher.satisfied_by(neg_nth(haystack.split(get_str!(split, job_state, StringMatcherError)), *n).ok_or(StringMatcherError::SegmentNotFound)?, job_state)?,
             Self::AnySegmentMatches {split, matcher} => {
@@ -425,6 +559,7 @@ impl StringMatcher {
                 haystack.strip_prefix(get_str!(value, job_state, StringMatcherError))
                     .is_some_and(|x| x.strip_prefix(split).is_some())
             },
+            Self::UrlPart {part, matcher} => matcher.satisfied_by(&part.get(job_state.url).ok_or(StringMatcherError::PartIsNone)?, job_state)?,
             Self::Common(common_call) => {
                 job_state.commons.string_matchers.get(get_str!(common_call.name, job_state, StringSourceError)).ok_or(StringMatcherError::CommonStringMatcherNotFound)?.satisfied_by(
                     haystack,
@@ -435,6 +570,8 @@ impl StringMatcher {
                         scratchpad: job_state.scratchpad,
                         #[cfg(feature = "cache")]
                         cache: job_state.cache,
+                        #[cfg(feature = "http")]
+                        rate_limiter: job_state.rate_limiter,
                         commons: job_state.commons,
                         common_args: Some(&common_call.args.make(job_state)?),
                         jobs_context: job_state.jobs_context
@@ -6,6 +6,7 @@ use std::collections::HashMap;
 
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use strum::IntoStaticStr;
 use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC, AsciiSet};
 #[expect(unused_imports, reason = "Used in a doc comment.")]
 #[cfg(feature = "regex")]
@@ -446,12 +447,38 @@ pub enum StringModification {
     /// If the call to [`String::from_utf8`] returns an error, that error is returned.
     #[cfg(feature = "base64")]
     Base64Decode(#[serde(default)] Base64Config),
-    /// [`serde_json::Value::pointer`].
-    /// Does not do any string conversions. I should probably add an option for that.
+    /// Parses the string as JSON then replaces it with the value found by [`serde_json::Value::pointer`].
+    ///
+    /// Strings are unquoted and numbers and booleans are converted to their JSON representations.
     /// # Errors
+    /// If the string isn't valid JSON, returns the error [`StringModificationError::SerdeJsonError`].
+    ///
     /// If the pointer doesn't point to anything, returns the error [`StringModificationError::JsonValueNotFound`].
     /// 
-    /// If the pointer points to a non-string value, returns the error [`StringModificationError::JsonValueIsNotAString`].
+    /// If the pointer points to a null, array, or object, returns the error [`StringModificationError::JsonValueIsNotAString`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// let json = r#"{"data": {"url": "https://example.com", "id": 123, "ok": true, "list": [1, 2]}}"#;
+    ///
+    /// let mut x = json.to_string();
+    /// StringModification::JsonPointer("/data/url".into()).apply(&mut x, &job_state.to_view()).unwrap();
+    /// assert_eq!(x, "https://example.com");
+    ///
+    /// let mut x = json.to_string();
+    /// StringModification::JsonPointer("/data/id".into()).apply(&mut x, &job_state.to_view()).unwrap();
+    /// assert_eq!(x, "123");
+    ///
+    /// let mut x = json.to_string();
+    /// StringModification::JsonPointer("/data/ok".into()).apply(&mut x, &job_state.to_view()).unwrap();
+    /// assert_eq!(x, "true");
+    ///
+    /// StringModification::JsonPointer("/data/list"   .into()).apply(&mut json.to_string(), &job_state.to_view()).unwrap_err();
+    /// StringModification::JsonPointer("/data/missing".into()).apply(&mut json.to_string(), &job_state.to_view()).unwrap_err();
+    /// StringModification::JsonPointer("/data/url"    .into()).apply(&mut "not json".to_string(), &job_state.to_view()).unwrap_err();
+    /// ```
     JsonPointer(StringSource),
     /// # Examples
     /// ```
@@ -997,7 +1024,8 @@ impl FromStr for StringModification {
 
 /// The enum of all possible errors [`StringModification::apply`] can return.
 #[allow(clippy::enum_variant_names, reason = "I disagree.")]
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "StringModificationError::")]
 pub enum StringModificationError {
     /// Returned when [`StringModification::Error`] is used.
     #[error("StringModification::Error was used.")]
@@ -1011,8 +1039,8 @@ pub enum StringModificationError {
     /// Returned when [`serde_json::Value::pointer`] returns [`None`].
     #[error("The requested JSON value was not found.")]
     JsonValueNotFound,
-    /// Returned when [`serde_json::Value::pointer`] returns a value that is not a string.
-    #[error("The requested JSON value was not a string.")]
+    /// Returned when [`serde_json::Value::pointer`] returns a value that is not a string, number, or boolean.
+    #[error("The requested JSON value was not a string, number, or boolean.")]
     JsonValueIsNotAString,
     /// Returned when the requested slice is either not on a UTF-8 boundary or out of bounds.
     #[error("The requested slice was either not on a UTF-8 boundary or out of bounds.")]
@@ -1091,6 +1119,17 @@ pub enum StringModificationError {
     Custom(Box<dyn std::error::Error + Send>)
 }
 
+impl ErrorCode for StringModificationError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::StringSourceError(e) => e.code(),
+            Self::StringMatcherError(e) => e.code(),
+            Self::CommonCallArgsError(e) => e.code(),
+            _ => self.into()
+        }
+    }
+}
+
 impl From<StringSourceError> for StringModificationError {
     fn from(value: StringSourceError) -> Self {
         Self::StringSourceError(Box::new(value))
@@ -1226,7 +1265,12 @@ impl StringModification {
             Self::UrlDecode => *to=percent_decode_str(to).decode_utf8()?.into_owned(),
             #[cfg(feature = "base64")] Self::Base64Encode(config) => *to = config.make_engine()?.encode(to.as_bytes()),
             #[cfg(feature = "base64")] Self::Base64Decode(config) => *to = String::from_utf8(config.make_engine()?.decode(to.as_bytes())?)?,
-            Self::JsonPointer(pointer) => *to = serde_json::from_str::<serde_json::Value>(to)?.pointer(get_str!(pointer, job_state, StringModificationError)).ok_or(StringModificationError::JsonValueNotFound)?.as_str().ok_or(StringModificationError::JsonValueIsNotAString)?.to_string(),
+            Self::JsonPointer(pointer) => *to = match serde_json::from_str::<serde_json::Value>(to)?.pointer(get_str!(pointer, job_state, StringModificationError)).ok_or(StringModificationError::JsonValueNotFound)? {
+                serde_json::Value::String(x) => x.clone(),
+                serde_json::Value::Number(x) => x.to_string(),
+                serde_json::Value::Bool  (x) => x.to_string(),
+                _ => Err(StringModificationError::JsonValueIsNotAString)?
+            },
 
 
 
@@ -1470,6 +1514,8 @@ impl StringModification {
                         scratchpad: job_state.scratchpad,
                         #[cfg(feature = "cache")]
                         cache: job_state.cache,
+                        #[cfg(feature = "http")]
+                        rate_limiter: job_state.rate_limiter,
                         commons: job_state.commons,
                         common_args: Some(&common_call.args.make(job_state)?),
                         jobs_context: job_state.jobs_context
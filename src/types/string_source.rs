@@ -9,6 +9,9 @@ use std::collections::HashMap;
 
 use serde::{Serialize, Deserialize};
 use thiserror::Error;
+use strum::IntoStaticStr;
+use url::Url;
+use percent_encoding::percent_decode_str;
 
 use crate::types::*;
 use crate::glue::*;
@@ -72,6 +75,50 @@ pub enum StringSource {
         /// The [`Self`] to use if [`Self::NoneTo::value`] returns [`None`].
         if_none: Box<Self>
     },
+    /// If the call to [`Self::get`] returns `Some("")`, instead return [`None`].
+    ///
+    /// The inverse of [`Self::NoneToEmptyString`].
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::EmptyToNone(Box::new("abc".into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("abc")));
+    /// assert_eq!(StringSource::EmptyToNone(Box::new(""   .into())).get(&job_state.to_view()).unwrap(), None);
+    /// assert_eq!(StringSource::EmptyToNone(Box::new(StringSource::None)).get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    EmptyToNone(Box<Self>),
+    /// If [`Self::Or::primary`] returns [`None`] or `Some("")`, instead return the value of [`Self::Or::fallback`].
+    ///
+    /// Unlike [`Self::NoneTo`], an empty string also uses the fallback.
+    ///
+    /// [`Self::Or::fallback`] is only gotten if it's used, and can still return [`None`] or `Some("")`.
+    /// # Errors
+    /// If either call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::Or {primary: Box::new("abc".into())     , fallback: Box::new("def".into())}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("abc")));
+    /// assert_eq!(StringSource::Or {primary: Box::new(""   .into())     , fallback: Box::new("def".into())}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("def")));
+    /// assert_eq!(StringSource::Or {primary: Box::new(StringSource::None), fallback: Box::new("def".into())}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("def")));
+    /// assert_eq!(StringSource::Or {primary: Box::new(StringSource::None), fallback: Box::new(StringSource::None)}.get(&job_state.to_view()).unwrap(), None);
+    ///
+    /// // The fallback isn't gotten if it isn't used.
+    /// assert_eq!(StringSource::Or {primary: Box::new("abc".into()), fallback: Box::new(StringSource::Error)}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("abc")));
+    /// StringSource::Or {primary: Box::new("".into()), fallback: Box::new(StringSource::Error)}.get(&job_state.to_view()).unwrap_err();
+    /// ```
+    Or {
+        /// The [`Self`] to use by default.
+        primary: Box<Self>,
+        /// The [`Self`] to use if [`Self::Or::primary`] returns [`None`] or `Some("")`.
+        fallback: Box<Self>
+    },
 
     // Logic.
 
@@ -235,6 +282,57 @@ pub enum StringSource {
     /// assert_eq!(StringSource::Part(UrlPart::Domain).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("example.com")));
     /// ```
     Part(UrlPart),
+    /// Gets the specified facet of the URL's host.
+    ///
+    /// If the URL doesn't have a host, returns [`None`].
+    ///
+    /// Useful for facets that aren't [`UrlPart`]s, like whether or not the host is a fully qualified domain name.
+    /// # Errors
+    /// Never returns an error.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state; url = "https://www.example.co.uk";);
+    ///
+    /// assert_eq!(StringSource::HostDetailsField(HostDetailsField::RegDomain   ).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("example.co.uk")));
+    /// assert_eq!(StringSource::HostDetailsField(HostDetailsField::Subdomain   ).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("www")));
+    /// assert_eq!(StringSource::HostDetailsField(HostDetailsField::DomainSuffix).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("co.uk")));
+    /// assert_eq!(StringSource::HostDetailsField(HostDetailsField::IsIp        ).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("false")));
+    /// assert_eq!(StringSource::HostDetailsField(HostDetailsField::IsFqdn      ).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("false")));
+    ///
+    /// *job_state.url = BetterUrl::parse("mailto:a@example.com").unwrap();
+    /// assert_eq!(StringSource::HostDetailsField(HostDetailsField::IsIp        ).get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    HostDetailsField(HostDetailsField),
+    /// Gets the URL's host with any punycode (`xn--`) labels decoded back to Unicode.
+    ///
+    /// This isn't a [`Mapper`] because [`url::Url`] always re-encodes domains to punycode, so a URL with a Unicode host can't exist.
+    /// Use this for display purposes, like putting the human readable host in a scratchpad var or the job's output, and never parse the result back into a URL expecting the same host.
+    ///
+    /// IP hosts and ASCII-only domains are returned unchanged.
+    ///
+    /// If the URL doesn't have a host, returns [`None`].
+    /// # Errors
+    /// Never returns an error.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state; url = "https://xn--nxasmq6b.example";);
+    ///
+    /// assert_eq!(StringSource::UnicodeHost.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("βόλοσ.example")));
+    ///
+    /// *job_state.url = BetterUrl::parse("https://www.example.com").unwrap();
+    /// assert_eq!(StringSource::UnicodeHost.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("www.example.com")));
+    ///
+    /// *job_state.url = BetterUrl::parse("https://[::1]").unwrap();
+    /// assert_eq!(StringSource::UnicodeHost.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("[::1]")));
+    ///
+    /// *job_state.url = BetterUrl::parse("mailto:a@example.com").unwrap();
+    /// assert_eq!(StringSource::UnicodeHost.get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    UnicodeHost,
     /// Parses `value` as a URL and gets the specified part.
     /// # Errors
     /// If the call to [`Self::get`] returns an error, that error is returned.
@@ -262,6 +360,308 @@ pub enum StringSource {
         /// The part to extract from `value`.
         part: UrlPart
     },
+    /// Parses `source` as a URL and gets the value of the first query parameter named `name`.
+    ///
+    /// Returns [`None`] if `source` returns [`None`] or the query parameter isn't found.
+    ///
+    /// Equivalent to using [`Self::ExtractPart`] with [`UrlPart::QueryParam`] but less verbose.
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    ///
+    /// If the call to [`Url::parse`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(
+    ///     StringSource::QueryParam {
+    ///         source: "https://t.co/x?url=https%3A%2F%2Fa.com".into(),
+    ///         name: "url".into()
+    ///     }.get(&job_state.to_view()).unwrap(),
+    ///     Some(Cow::Borrowed("https://a.com"))
+    /// );
+    /// assert_eq!(
+    ///     StringSource::QueryParam {
+    ///         source: "https://t.co/x?url=https%3A%2F%2Fa.com".into(),
+    ///         name: "abc".into()
+    ///     }.get(&job_state.to_view()).unwrap(),
+    ///     None
+    /// );
+    ///
+    /// StringSource::QueryParam {source: "not a url".into(), name: "url".into()}.get(&job_state.to_view()).unwrap_err();
+    /// ```
+    QueryParam {
+        /// The string to parse as a URL.
+        source: Box<Self>,
+        /// The name of the query parameter to get.
+        name: String
+    },
+    /// Splits `source` on `sep` and gets the `index`th segment.
+    ///
+    /// Negative indices count from the end, so `-1` is the last segment.
+    ///
+    /// Returns [`None`] if `source` returns [`None`] or `index` is out of range.
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::Split {source: "a/b/c".into(), sep: "/".into(), index:  0}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("a")));
+    /// assert_eq!(StringSource::Split {source: "a/b/c".into(), sep: "/".into(), index:  2}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("c")));
+    /// assert_eq!(StringSource::Split {source: "a/b/c".into(), sep: "/".into(), index: -1}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("c")));
+    /// assert_eq!(StringSource::Split {source: "a/b/c".into(), sep: "/".into(), index: -3}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("a")));
+    ///
+    /// assert_eq!(StringSource::Split {source: "a/b/c".into(), sep: "/".into(), index:  3}.get(&job_state.to_view()).unwrap(), None);
+    /// assert_eq!(StringSource::Split {source: "a/b/c".into(), sep: "/".into(), index: -4}.get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    Split {
+        /// The string to split.
+        source: Box<Self>,
+        /// The separator to split `source` on.
+        sep: String,
+        /// The index of the segment to get.
+        index: isize
+    },
+    /// Replaces `from` with `to` in `source`.
+    ///
+    /// If `all` is [`false`] (the default), only replaces the first occurrence.
+    ///
+    /// Unlike [`StringModification::Replace`], this can handle `source` being [`None`], in which case it returns [`None`].
+    /// # Errors
+    /// If any call to [`Self::get`] returns an error, that error is returned.
+    ///
+    /// If either call to [`Self::get`] for `from` and `to` returns [`None`], returns the error [`StringSourceError::StringSourceIsNone`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::Replace {source: "a-b-c".into(), from: "-".into(), to: "/".into(), all: false}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("a/b-c")));
+    /// assert_eq!(StringSource::Replace {source: "a-b-c".into(), from: "-".into(), to: "/".into(), all: true }.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("a/b/c")));
+    /// assert_eq!(StringSource::Replace {source: Box::new(StringSource::None), from: "-".into(), to: "/".into(), all: true}.get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    Replace {
+        /// The string to replace substrings in.
+        source: Box<Self>,
+        /// The substring to replace.
+        from: Box<Self>,
+        /// The string to replace `from` with.
+        to: Box<Self>,
+        /// If [`true`], replaces all occurrences of `from` instead of just the first.
+        ///
+        /// Defaults to [`false`].
+        #[serde(default, skip_serializing_if = "is_default")]
+        all: bool
+    },
+    /// Gets the substring of `source` from the `start`th char up to but not including the `end`th char.
+    ///
+    /// Indices count [`char`]s, not bytes, so slicing never splits a multi-byte character and never panics. Emoji made of multiple [`char`]s (like flags and skin tones) can still be split.
+    ///
+    /// Negative indices count from the end, like in Python. If `end` is [`None`], slices to the end of `source`.
+    ///
+    /// If either index is out of range or `start` is after `end`, returns [`None`] instead of clamping.
+    ///
+    /// If `source` is [`None`], returns [`None`].
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::Slice {source: "a🦀b🦀c".into(), start:  0, end: Some( 2)}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("a🦀")));
+    /// assert_eq!(StringSource::Slice {source: "a🦀b🦀c".into(), start:  1, end: Some( 4)}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("🦀b🦀")));
+    /// assert_eq!(StringSource::Slice {source: "a🦀b🦀c".into(), start: -2, end: None    }.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("🦀c")));
+    /// assert_eq!(StringSource::Slice {source: "a🦀b🦀c".into(), start:  2, end: Some(-1)}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("b🦀")));
+    /// assert_eq!(StringSource::Slice {source: "a🦀b🦀c".into(), start:  5, end: None    }.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("")));
+    ///
+    /// assert_eq!(StringSource::Slice {source: "a🦀b🦀c".into(), start:  6, end: None    }.get(&job_state.to_view()).unwrap(), None);
+    /// assert_eq!(StringSource::Slice {source: "a🦀b🦀c".into(), start:  0, end: Some( 6)}.get(&job_state.to_view()).unwrap(), None);
+    /// assert_eq!(StringSource::Slice {source: "a🦀b🦀c".into(), start: -6, end: None    }.get(&job_state.to_view()).unwrap(), None);
+    /// assert_eq!(StringSource::Slice {source: "a🦀b🦀c".into(), start:  3, end: Some( 2)}.get(&job_state.to_view()).unwrap(), None);
+    /// assert_eq!(StringSource::Slice {source: Box::new(StringSource::None), start: 0, end: None}.get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    Slice {
+        /// The string to slice.
+        source: Box<Self>,
+        /// The index of the first [`char`] to keep.
+        start: isize,
+        /// The index of the first [`char`] after `start` to not keep.
+        ///
+        /// If [`None`], keeps everything after `start`.
+        ///
+        /// Defaults to [`None`].
+        #[serde(default, skip_serializing_if = "is_default")]
+        end: Option<isize>
+    },
+    /// Gets the length of `source` in [`char`]s as a decimal string.
+    ///
+    /// Counts [`char`]s instead of bytes to be consistent with [`Self::Slice`]. Note that [`StringMatcher::LengthIs`] counts bytes.
+    ///
+    /// If `source` is [`None`], returns [`None`].
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::Length(Box::new("abc"  .into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("3")));
+    /// assert_eq!(StringSource::Length(Box::new("a🦀b" .into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("3")));
+    /// assert_eq!(StringSource::Length(Box::new(""     .into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("0")));
+    /// assert_eq!(StringSource::Length(Box::new(StringSource::None)).get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    Length(Box<Self>),
+    /// Pads the start of `source` with `fill` until it's at least `len` [`char`]s long.
+    ///
+    /// Length is measured in [`char`]s, not bytes, like [`Self::Length`]. If `source` is already at least `len` [`char`]s long, it's returned unchanged.
+    ///
+    /// If `source` is [`None`], returns [`None`].
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::PadStart {source: Box::new("42"   .into()), len: 5, fill: '0'}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("00042")));
+    /// assert_eq!(StringSource::PadStart {source: Box::new("é"    .into()), len: 3, fill: ' '}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("  é")));
+    /// assert_eq!(StringSource::PadStart {source: Box::new("abcde".into()), len: 3, fill: '0'}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("abcde")));
+    /// assert_eq!(StringSource::PadStart {source: Box::new(StringSource::None), len: 3, fill: '0'}.get(&job_state.to_view()).unwrap(), None);
+    ///
+    /// assert_eq!(serde_json::from_str::<StringSource>(r#"{"PadStart": {"source": "a", "len": 2}}"#).unwrap(), StringSource::PadStart {source: Box::new("a".into()), len: 2, fill: ' '});
+    /// ```
+    PadStart {
+        /// The string to pad.
+        source: Box<Self>,
+        /// The minimum length in [`char`]s.
+        len: usize,
+        /// The [`char`] to pad with.
+        ///
+        /// Defaults to a space.
+        #[serde(default = "get_space", skip_serializing_if = "is_space")]
+        fill: char
+    },
+    /// Pads the end of `source` with `fill` until it's at least `len` [`char`]s long.
+    ///
+    /// Length is measured in [`char`]s, not bytes, like [`Self::Length`]. If `source` is already at least `len` [`char`]s long, it's returned unchanged.
+    ///
+    /// If `source` is [`None`], returns [`None`].
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::PadEnd {source: Box::new("ab"   .into()), len: 4, fill: '-'}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("ab--")));
+    /// assert_eq!(StringSource::PadEnd {source: Box::new("🦀"   .into()), len: 2, fill: '.'}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("🦀.")));
+    /// assert_eq!(StringSource::PadEnd {source: Box::new("abcde".into()), len: 3, fill: '-'}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("abcde")));
+    /// assert_eq!(StringSource::PadEnd {source: Box::new(StringSource::None), len: 3, fill: '-'}.get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    PadEnd {
+        /// The string to pad.
+        source: Box<Self>,
+        /// The minimum length in [`char`]s.
+        len: usize,
+        /// The [`char`] to pad with.
+        ///
+        /// Defaults to a space.
+        #[serde(default = "get_space", skip_serializing_if = "is_space")]
+        fill: char
+    },
+    /// Repeats `source` `count` times. Effectively [`str::repeat`].
+    ///
+    /// If `count` is `0`, returns an empty string.
+    ///
+    /// If `source` is [`None`], returns [`None`].
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::Repeat {source: Box::new("ab".into()), count: 3}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("ababab")));
+    /// assert_eq!(StringSource::Repeat {source: Box::new("ab".into()), count: 1}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("ab")));
+    /// assert_eq!(StringSource::Repeat {source: Box::new("ab".into()), count: 0}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("")));
+    /// assert_eq!(StringSource::Repeat {source: Box::new(StringSource::None), count: 3}.get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    Repeat {
+        /// The string to repeat.
+        source: Box<Self>,
+        /// The amount of times to repeat it.
+        count: usize
+    },
+    /// Percent decodes `source`, but if the result isn't valid UTF-8, returns `source` unchanged.
+    ///
+    /// Unlike [`StringModification::UrlDecode`], this never returns an error for invalid UTF-8 and never replaces invalid bytes with `�`.
+    ///
+    /// Doesn't decode `+` into a space.
+    ///
+    /// If `source` is [`None`], returns [`None`].
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::TryUrlDecode(Box::new("a%20b%F0%9F%A6%80".into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("a b🦀")));
+    /// assert_eq!(StringSource::TryUrlDecode(Box::new("a+b"              .into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("a+b")));
+    /// // `%FF` isn't valid UTF-8.
+    /// assert_eq!(StringSource::TryUrlDecode(Box::new("a%20b%FF"         .into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("a%20b%FF")));
+    /// assert_eq!(StringSource::TryUrlDecode(Box::new(StringSource::None         )).get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    TryUrlDecode(Box<Self>),
+    /// Hashes the value of `source` with `algorithm` and returns the digest as lowercase hex.
+    ///
+    /// Useful for making fixed length cache keys from arbitrarily long values.
+    ///
+    /// If the call to [`Self::get`] returns [`None`], returns [`None`].
+    ///
+    /// Requires the `hash` feature.
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use url_cleaner::glue::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(
+    ///     StringSource::Hash {source: Box::new("abc".into()), algorithm: HashAlgorithm::Sha256}.get(&job_state.to_view()).unwrap(),
+    ///     Some(Cow::Borrowed("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"))
+    /// );
+    /// assert_eq!(
+    ///     StringSource::Hash {source: Box::new(StringSource::Part(UrlPart::Whole)), algorithm: HashAlgorithm::Md5}.get(&job_state.to_view()).unwrap().map(|x| x.len()),
+    ///     Some(32)
+    /// );
+    /// assert_eq!(StringSource::Hash {source: Box::new(StringSource::None), algorithm: HashAlgorithm::Sha256}.get(&job_state.to_view()).unwrap(), None);
+    /// ```
+    #[cfg(feature = "hash")]
+    Hash {
+        /// The value to hash.
+        source: Box<Self>,
+        /// The algorithm to hash with.
+        ///
+        /// Defaults to [`HashAlgorithm::Sha256`].
+        #[serde(default, skip_serializing_if = "is_default")]
+        algorithm: HashAlgorithm
+    },
     /// Indexes [`JobState::common_args`].
     /// # Errors
     /// If [`JobState::common_args`] is [`None`], returns the error [`StringSourceError::NotInACommonContext`].
@@ -299,8 +699,19 @@ pub enum StringSource {
     /// Gets the value of the specified [`JobsContext::vars`]
     ///
     /// Returns [`None`] (NOT an error) if the var is not set.
+    ///
+    /// The CLI's [`JobsContext`] is set with `--jobs-context`, like `--jobs-context '{"vars":{"batch":"x"}}'`.
     /// # Errors
     /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state; jobs_context = serde_json::from_str::<JobsContext>(r#"{"vars":{"batch":"x"}}"#).unwrap(););
+    ///
+    /// assert_eq!(StringSource::JobsContextVar(Box::new("batch".into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("x")));
+    /// assert_eq!(StringSource::JobsContextVar(Box::new("other".into())).get(&job_state.to_view()).unwrap(), None);
+    /// ```
     JobsContextVar(#[suitable(assert = "jobs_context_var_is_documented")] Box<Self>),
     /// Indexes into a [`Params::maps`] using `map` then indexes the returned [`HashMap`] with `key`.
     /// # Errors
@@ -344,16 +755,158 @@ pub enum StringSource {
     /// 
     /// If the call to [`std::env::var`] returns the error [`std::env::VarError::NotUnicode`], returns the error [`StringSourceError::EnvVarIsNotUtf8`].
     EnvVar(#[suitable(assert = "env_var_is_documented")] Box<Self>),
+    /// Reads the file at `path` and returns its contents.
+    ///
+    /// Useful for getting secrets, like tokens for signing requests, without putting them in the config.
+    ///
+    /// If `trim` is [`true`], leading and trailing whitespace (like the newline most editors put at the end of files) is removed.
+    ///
+    /// Because configs using this can read any file the user can, it never passes `--test-suitability`.
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    ///
+    /// If the call to [`Self::get`] returns [`None`], returns the error [`StringSourceError::StringSourceIsNone`].
+    ///
+    /// If the call to [`std::fs::read_to_string`] returns an error, returns the error [`StringSourceError::IoError`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// let path = std::env::temp_dir().join(format!("url-cleaner-read-file-doctest-{}", std::process::id()));
+    /// std::fs::write(&path, "secret\n").unwrap();
+    /// let path_str = path.to_str().unwrap();
+    ///
+    /// assert_eq!(StringSource::ReadFile {path: Box::new(path_str.into()), trim: false}.get(&job_state.to_view()).unwrap().as_deref(), Some("secret\n"));
+    /// assert_eq!(StringSource::ReadFile {path: Box::new(path_str.into()), trim: true }.get(&job_state.to_view()).unwrap().as_deref(), Some("secret"));
+    ///
+    /// std::fs::remove_file(&path).unwrap();
+    /// assert!(matches!(StringSource::ReadFile {path: Box::new(path_str.into()), trim: false}.get(&job_state.to_view()), Err(StringSourceError::IoError(_))));
+    /// ```
+    #[cfg(feature = "fs")]
+    #[suitable(never)]
+    ReadFile {
+        /// The path of the file to read.
+        path: Box<Self>,
+        /// If [`true`], remove leading and trailing whitespace.
+        ///
+        /// Defaults to [`false`].
+        #[serde(default, skip_serializing_if = "is_default")]
+        trim: bool
+    },
     /// Sends an HTTP request and returns a string from the response determined by the specified [`ResponseHandler`].
+    ///
+    /// If the `async` feature is enabled, the request is sent with `AsyncHttpRunner::global`'s `AsyncHttpRunner::response` instead of [`RequestConfig::response`].
     /// # Errors
     /// If the call to [`RequestConfig::response`] returns an error, that error is returned.
+    ///
+    /// If the call to `AsyncHttpRunner::response` returns an error, that error is returned.
     #[cfg(feature = "http")]
     HttpRequest(Box<RequestConfig>),
+    /// Sends an HTTP request, parses the string from [`RequestConfig::response_handler`] as JSON, and returns the value at [`Self::RequestJson::pointer`].
+    ///
+    /// Uses [`serde_json::Value::pointer`], so `pointer` is a [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) like `/data/url`.
+    ///
+    /// If the value is a string, it's returned without quotes. If it's `null`, returns [`None`]. Otherwise, returns the value as JSON.
+    ///
+    /// Like [`Self::HttpRequest`], if the `async` feature is enabled, the request is sent with `AsyncHttpRunner::global`.
+    /// # Errors
+    /// If the call to [`RequestConfig::response`] returns an error, that error is returned.
+    ///
+    /// If the call to `AsyncHttpRunner::response` returns an error, that error is returned.
+    ///
+    /// If the response isn't valid JSON, returns the error [`StringSourceError::ResponseIsNotJson`].
+    ///
+    /// If the value at `pointer` doesn't exist, returns the error [`StringSourceError::JsonPointerNotFound`].
+    /// # Examples
+    /// ```
+    /// # use std::io::{Read, Write};
+    /// # use url_cleaner::types::*;
+    /// # use url_cleaner::glue::*;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// /// Serves `body` once and returns the URL to request.
+    /// fn serve_once(body: &'static str) -> String {
+    ///     let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    ///     let url = format!("http://{}/", listener.local_addr().unwrap());
+    ///     std::thread::spawn(move || {
+    ///         let (mut stream, _) = listener.accept().unwrap();
+    ///         let _ = stream.read(&mut [0u8; 4096]).unwrap();
+    ///         write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()).unwrap();
+    ///     });
+    ///     url
+    /// }
+    ///
+    /// let request_json = |body, pointer: &str| StringSource::RequestJson {
+    ///     request: Box::new(RequestConfig {url: serve_once(body).into(), ..Default::default()}),
+    ///     pointer: pointer.into()
+    /// };
+    ///
+    /// assert_eq!(request_json(r#"{"url":"https://x"}"#, "/url"  ).get(&job_state.to_view()).unwrap().as_deref(), Some("https://x"));
+    /// assert_eq!(request_json(r#"{"a":[1, true]}"#    , "/a/1"  ).get(&job_state.to_view()).unwrap().as_deref(), Some("true"));
+    /// assert_eq!(request_json(r#"{"url":null}"#       , "/url"  ).get(&job_state.to_view()).unwrap().as_deref(), None);
+    ///
+    /// assert!(matches!(request_json(r#"{"url":"https://x"}"#, "/other").get(&job_state.to_view()), Err(StringSourceError::JsonPointerNotFound)));
+    /// assert!(matches!(request_json("https://x"             , "/url"  ).get(&job_state.to_view()), Err(StringSourceError::ResponseIsNotJson(_))));
+    /// assert!(matches!(StringSource::RequestJson {
+    ///     request: Box::new(RequestConfig {url: "not a url".into(), ..Default::default()}),
+    ///     pointer: "/url".into()
+    /// }.get(&job_state.to_view()), Err(StringSourceError::RequestConfigError(_))));
+    /// ```
+    #[cfg(feature = "http")]
+    RequestJson {
+        /// The request to send.
+        request: Box<RequestConfig>,
+        /// The JSON pointer to the value to return.
+        pointer: String
+    },
     /// Run a command and return its output.
     /// # Errors
     /// If the call to [`CommandConfig::output`] returns an error, that error is returned.
     #[cfg(feature = "commands")]
     CommandOutput(Box<CommandConfig>),
+    /// Runs a command and returns its STDOUT.
+    ///
+    /// Unlike [`Self::CommandOutput`], trailing newlines and carriage returns are trimmed by default and a non-zero exit code is an error by default.
+    /// # Errors
+    /// If the call to [`CommandConfig::output_and_status`] returns an error, that error is returned.
+    ///
+    /// If the command doesn't exit successfully and `allow_failure` is `false`, returns the error [`StringSourceError::CommandFailed`].
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use url_cleaner::glue::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// let echo = CommandConfig {program: "/bin/echo".into(), args: vec!["hello".into()], current_dir: None, envs: Default::default(), stdin: None};
+    ///
+    /// assert_eq!(StringSource::Command {command: Box::new(echo.clone()), trim: true , allow_failure: false}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("hello")));
+    /// assert_eq!(StringSource::Command {command: Box::new(echo.clone()), trim: false, allow_failure: false}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("hello\n")));
+    ///
+    /// let fail = CommandConfig {program: "/bin/sh".into(), args: vec!["-c".into(), "echo partial; exit 3".into()], current_dir: None, envs: Default::default(), stdin: None};
+    ///
+    /// StringSource::Command {command: Box::new(fail.clone()), trim: true, allow_failure: false}.get(&job_state.to_view()).unwrap_err();
+    /// assert_eq!(StringSource::Command {command: Box::new(fail.clone()), trim: true, allow_failure: true}.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("partial")));
+    ///
+    /// let source: StringSource = serde_json::from_str(r#"{"Command": {"command": "/bin/echo"}}"#).unwrap();
+    /// assert_eq!(source, StringSource::Command {command: Box::new("/bin/echo".into()), trim: true, allow_failure: false});
+    /// ```
+    #[cfg(feature = "commands")]
+    Command {
+        /// The command to run.
+        command: Box<CommandConfig>,
+        /// If `true`, trims trailing newlines and carriage returns from the output.
+        ///
+        /// Defaults to `true`.
+        #[serde(default = "get_true", skip_serializing_if = "is_true")]
+        trim: bool,
+        /// If `true`, returns the output even if the command doesn't exit successfully.
+        ///
+        /// Defaults to `false`.
+        #[serde(default, skip_serializing_if = "is_default")]
+        allow_failure: bool
+    },
     /// Read from the cache.
     /// 
     /// If an entry is found, return its value.
@@ -376,6 +929,70 @@ pub enum StringSource {
         /// The [`Self`] to cache.
         value: Box<Self>
     },
+    /// Gets the value of the contained [`Self`] once per job and reuses it for every subsequent call in that job.
+    ///
+    /// Useful for avoiding duplicate HTTP requests when the same [`Self`] is used in multiple places without needing the `cache` feature.
+    ///
+    /// Values are stored in [`JobScratchpad::memos`] keyed by the contained [`Self`] and the current [`CommonCallArgs`].
+    /// This means two separate but equal [`Self::Memoized`]s, such as one in a condition and one in a mapper, share a value, but calling the same common with different arguments gets separate values.
+    ///
+    /// Please note that the value is not recomputed if the URL changes, so memoizing a [`Self`] that depends on the URL is usually a mistake.
+    ///
+    /// Errors are not memoized.
+    /// # Errors
+    /// If the call to [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state;);
+    ///
+    /// assert_eq!(StringSource::Memoized(Box::new("abc".into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("abc")));
+    /// assert_eq!(StringSource::Memoized(Box::new(StringSource::None)).get(&job_state.to_view()).unwrap(), None);
+    ///
+    /// // The contained `StringSource` is only gotten once.
+    /// let source = StringSource::Memoized(Box::new(StringSource::ScratchpadVar(Box::new("a".into()))));
+    /// job_state.scratchpad.vars.insert("a".into(), "1".into());
+    /// assert_eq!(source.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("1")));
+    /// job_state.scratchpad.vars.insert("a".into(), "2".into());
+    /// assert_eq!(source.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("1")));
+    /// assert_eq!(StringSource::ScratchpadVar(Box::new("a".into())).get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("2")));
+    ///
+    /// // Each set of common args gets its own value.
+    /// let commons: Commons = serde_json::from_str(r#"{"string_sources": {"a": {"Memoized": {"CommonVar": "x"}}}}"#).unwrap();
+    /// url_cleaner::job_state!(job_state; commons = commons;);
+    /// let one: StringSource = serde_json::from_str(r#"{"Common": {"name": "a", "args": {"vars": {"x": "1"}}}}"#).unwrap();
+    /// let two: StringSource = serde_json::from_str(r#"{"Common": {"name": "a", "args": {"vars": {"x": "2"}}}}"#).unwrap();
+    /// assert_eq!(one.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("1")));
+    /// assert_eq!(two.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("2")));
+    ///
+    /// # #[cfg(feature = "custom")]
+    /// # {
+    /// # use url_cleaner::glue::*;
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// static CALLS: AtomicUsize = AtomicUsize::new(0);
+    /// fn counting(_: &JobStateView) -> Result<Option<Cow<'static, str>>, StringSourceError> {
+    ///     CALLS.fetch_add(1, Ordering::SeqCst);
+    ///     Ok(Some(Cow::Borrowed("expensive")))
+    /// }
+    ///
+    /// let source = StringSource::Memoized(Box::new(StringSource::Custom(FnWrapper(counting))));
+    /// assert_eq!(source.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("expensive")));
+    /// assert_eq!(source.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("expensive")));
+    /// assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    ///
+    /// // Separate but equal `Memoized`s share the value.
+    /// let equal_source = StringSource::Memoized(Box::new(StringSource::Custom(FnWrapper(counting))));
+    /// assert!(!std::ptr::eq(&source, &equal_source));
+    /// assert_eq!(equal_source.get(&job_state.to_view()).unwrap(), Some(Cow::Borrowed("expensive")));
+    /// assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    ///
+    /// // Without memoization it's called every time.
+    /// StringSource::Custom(FnWrapper(counting)).get(&job_state.to_view()).unwrap();
+    /// assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+    /// # }
+    /// ```
+    Memoized(Box<Self>),
     /// Extracts the substring of `value` found between the first `start` and the first subsequent `end`.
     /// 
     /// The same as [`StringModification::ExtractBetween`] but preserves borrowedness.
@@ -410,6 +1027,29 @@ pub enum StringSource {
         regex: RegexWrapper
     },
     /// Uses a [`Self`] from the [`JobState::commons`]'s [`Commons::string_sources`].
+    ///
+    /// The [`CommonCall::args`] are accessible in the common [`Self`] via [`Self::CommonVar`] and [`Condition::CommonFlagIsSet`].
+    /// # Errors
+    /// If the call to [`Self::get`] for [`CommonCall::name`] returns an error, that error is returned.
+    ///
+    /// If the common [`Self`] isn't found, returns the error [`StringSourceError::CommonStringSourceNotFound`].
+    ///
+    /// If the call to [`CommonCallArgsSource::make`] returns an error, that error is returned.
+    ///
+    /// If the call to the common [`Self`]'s [`Self::get`] returns an error, that error is returned.
+    /// # Examples
+    /// ```
+    /// # use url_cleaner::types::*;
+    /// # use std::borrow::Cow;
+    /// url_cleaner::job_state!(job_state; commons = serde_json::from_str(r#"{"string_sources": {"greet": {"Join": {"sources": ["Hello, ", {"CommonVar": "name"}, "!"]}}}}"#).unwrap(););
+    ///
+    /// assert_eq!(
+    ///     StringSource::Common(serde_json::from_str(r#"{"name": "greet", "args": {"vars": {"name": {"Part": "Host"}}}}"#).unwrap()).get(&job_state.to_view()).unwrap(),
+    ///     Some(Cow::Borrowed("Hello, example.com!"))
+    /// );
+    ///
+    /// StringSource::Common(serde_json::from_str(r#""not-a-common""#).unwrap()).get(&job_state.to_view()).unwrap_err();
+    /// ```
     Common(CommonCall),
     /// Uses a function pointer.
     /// 
@@ -505,9 +1145,15 @@ impl<'de> Deserialize<'de> for StringSource {
     }
 }
 
+/// Serde helper function. The default value of [`StringSource::PadStart::fill`] and [`StringSource::PadEnd::fill`].
+const fn get_space() -> char {' '}
+/// Serde helper function. Checks if `x` is the default value of [`StringSource::PadStart::fill`] and [`StringSource::PadEnd::fill`].
+const fn is_space(x: &char) -> bool {*x == ' '}
+
 /// The enum of all possible errors [`StringSource::get`] can return.
 #[allow(clippy::enum_variant_names, reason = "I disagree.")]
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
+#[strum(prefix = "StringSourceError::")]
 pub enum StringSourceError {
     /// Returned when [`StringSource::Error`] is used.
     #[error("StringSource::Error was used.")]
@@ -529,6 +1175,14 @@ pub enum StringSourceError {
     /// Returned when a call to [`StringSource::get`] returns `None` where it has to be `Some`.
     #[error("The specified StringSource returned None where it had to be Some.")]
     StringSourceIsNone,
+    /// Returned when a [`StringSource::RequestJson`]'s response isn't valid JSON.
+    #[cfg(feature = "http")]
+    #[error("The response wasn't valid JSON: {0}")]
+    ResponseIsNotJson(serde_json::Error),
+    /// Returned when a [`StringSource::RequestJson`]'s JSON pointer isn't found in the response.
+    #[cfg(feature = "http")]
+    #[error("The JSON pointer wasn't found in the response.")]
+    JsonPointerNotFound,
     /// Returned when a [`RequestConfigError`] is encountered.
     #[cfg(feature = "http")]
     #[error(transparent)]
@@ -541,6 +1195,12 @@ pub enum StringSourceError {
     #[cfg(feature = "commands")]
     #[error(transparent)]
     CommandError(Box<CommandError>),
+    /// Returned when the command of a [`StringSource::Command`] doesn't exit successfully.
+    ///
+    /// Contains the exit code, if any.
+    #[cfg(feature = "commands")]
+    #[error("The command exited unsuccessfully with exit code {0:?}.")]
+    CommandFailed(Option<i32>),
     /// Returned when the provided string is not in the specified map.
     #[error("The provided string was not in the specified map.")]
     StringNotInMap,
@@ -555,6 +1215,10 @@ pub enum StringSourceError {
     /// Returned when a [`StringMatcherError`] is encountered.
     #[error(transparent)]
     StringMatcherError(#[from] Box<StringMatcherError>),
+    /// Returned when a [`std::io::Error`] is encountered.
+    #[cfg(feature = "fs")]
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
     /// Returned when the value of a requested environment variable is not UTF-8.
     #[error("The value of the requested environment variable was not UTF-8.")]
     EnvVarIsNotUtf8,
@@ -589,6 +1253,16 @@ pub enum StringSourceError {
     Custom(Box<dyn std::error::Error + Send>)
 }
 
+impl ErrorCode for StringSourceError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::StringModificationError(e) => e.code(),
+            Self::StringMatcherError(e) => e.code(),
+            Self::CommonCallArgsError(e) => e.code(),
+            _ => self.into()
+        }
+    }
+}
 
 #[cfg(feature = "commands")]
 impl From<CommandError> for StringSourceError {
@@ -623,6 +1297,11 @@ impl StringSource {
             },
             Self::NoneToEmptyString(value) => value.get(job_state)?.or(Some(Cow::Borrowed(""))),
             Self::NoneTo {value, if_none} => value.get(job_state).transpose().or_else(|| if_none.get(job_state).transpose()).transpose()?,
+            Self::EmptyToNone(value) => value.get(job_state)?.filter(|value| !value.is_empty()),
+            Self::Or {primary, fallback} => match primary.get(job_state)?.filter(|value| !value.is_empty()) {
+                Some(value) => Some(value),
+                None => fallback.get(job_state)?
+            },
 
 
 
@@ -651,7 +1330,57 @@ impl StringSource {
 
 
             Self::Part(part) => part.get(job_state.url),
+            Self::HostDetailsField(field) => field.get(job_state.url),
+            Self::UnicodeHost => match job_state.url.host_and_details() {
+                Some((host, HostDetails::Domain(_))) if has_punycode_label(host) => Some(Cow::Owned(idna::domain_to_unicode(host).0)),
+                Some((host, _)) => Some(Cow::Borrowed(host)),
+                None => None
+            },
             Self::ExtractPart{value, part} => value.get(job_state)?.map(|url_str| BetterUrl::parse(&url_str)).transpose()?.and_then(|url| part.get(&url).map(|part_value| Cow::Owned(part_value.into_owned()))),
+            Self::Split {source, sep, index} => match source.get(job_state)? {
+                Some(Cow::Borrowed(x)) => neg_nth(x.split(sep.as_str()), *index).map(Cow::Borrowed),
+                Some(Cow::Owned(x)) => neg_nth(x.split(sep.as_str()), *index).map(|segment| Cow::Owned(segment.to_string())),
+                None => None
+            },
+            Self::Replace {source, from, to, all} => match source.get(job_state)? {
+                Some(source) => {
+                    let from = get_str!(from, job_state, StringSourceError);
+                    let to = get_str!(to, job_state, StringSourceError);
+                    Some(Cow::Owned(if *all {source.replace(from, to)} else {source.replacen(from, to, 1)}))
+                },
+                None => None
+            },
+            Self::Length(source) => source.get(job_state)?.map(|x| Cow::Owned(x.chars().count().to_string())),
+            Self::PadStart {source, len, fill} => source.get(job_state)?.map(|x| match len.checked_sub(x.chars().count()) {
+                Some(padding @ 1..) => Cow::Owned(std::iter::repeat_n(*fill, padding).chain(x.chars()).collect()),
+                _ => x
+            }),
+            Self::PadEnd {source, len, fill} => source.get(job_state)?.map(|x| match len.checked_sub(x.chars().count()) {
+                Some(padding @ 1..) => Cow::Owned(x.chars().chain(std::iter::repeat_n(*fill, padding)).collect()),
+                _ => x
+            }),
+            Self::Repeat {source, count} => source.get(job_state)?.map(|x| match count {
+                1 => x,
+                _ => Cow::Owned(x.repeat(*count))
+            }),
+            Self::TryUrlDecode(source) => source.get(job_state)?.map(|x| {
+                let decoded = match percent_decode_str(&x).decode_utf8() {
+                    Ok(Cow::Owned(decoded)) => Some(decoded),
+                    Ok(Cow::Borrowed(_)) | Err(_) => None
+                };
+                decoded.map_or(x, Cow::Owned)
+            }),
+            #[cfg(feature = "hash")]
+            Self::Hash {source, algorithm} => source.get(job_state)?.map(|x| Cow::Owned(algorithm.hash(&x))),
+            Self::Slice {source, start, end} => match source.get(job_state)? {
+                Some(Cow::Borrowed(x)) => char_slice(x, *start, *end).map(Cow::Borrowed),
+                Some(Cow::Owned(x)) => char_slice(&x, *start, *end).map(|x| Cow::Owned(x.to_string())),
+                None => None
+            },
+            Self::QueryParam {source, name} => match source.get(job_state)? {
+                Some(url) => Url::parse(&url)?.query_pairs().find(|(x, _)| x == name).map(|(_, value)| Cow::Owned(value.into_owned())),
+                None => None
+            },
             Self::CommonVar(name) => job_state.common_args.ok_or(StringSourceError::NotInACommonContext)?.vars.get(get_str!(name, job_state, StringSourceError)).map(|value| Cow::Borrowed(value.as_str())),
             Self::Var(key) => job_state.params.vars.get(get_str!(key, job_state, StringSourceError)).map(|value| Cow::Borrowed(value.as_str())),
             Self::ScratchpadVar(key) => job_state.scratchpad.vars.get(get_str!(key, job_state, StringSourceError)).map(|value| Cow::Borrowed(&**value)),
@@ -686,10 +1415,37 @@ impl StringSource {
                     Err(std::env::VarError::NotUnicode(_)) => Err(StringSourceError::EnvVarIsNotUtf8)?
                 }
             },
+            #[cfg(feature = "fs")]
+            Self::ReadFile {path, trim} => {
+                let contents = std::fs::read_to_string(get_str!(path, job_state, StringSourceError))?;
+                Some(Cow::Owned(if *trim {contents.trim().to_string()} else {contents}))
+            },
             #[cfg(feature = "http")]
+            #[cfg(not(feature = "async"))]
             Self::HttpRequest(config) => Some(Cow::Owned(config.response(job_state)?)),
+            #[cfg(feature = "async")]
+            Self::HttpRequest(config) => Some(Cow::Owned(AsyncHttpRunner::global().response(config, job_state)?)),
+            #[cfg(feature = "http")]
+            Self::RequestJson {request, pointer} => {
+                #[cfg(not(feature = "async"))]
+                let response = request.response(job_state)?;
+                #[cfg(feature = "async")]
+                let response = AsyncHttpRunner::global().response(request, job_state)?;
+                match serde_json::from_str::<serde_json::Value>(&response).map_err(StringSourceError::ResponseIsNotJson)?.pointer(pointer).ok_or(StringSourceError::JsonPointerNotFound)? {
+                    serde_json::Value::Null => None,
+                    serde_json::Value::String(value) => Some(Cow::Owned(value.clone())),
+                    value => Some(Cow::Owned(value.to_string()))
+                }
+            },
             #[cfg(feature = "commands")]
             Self::CommandOutput(command) => Some(Cow::Owned(command.output(job_state)?)),
+            #[cfg(feature = "commands")]
+            Self::Command {command, trim, allow_failure} => {
+                let (mut output, status) = command.output_and_status(job_state)?;
+                if !status.success() && !allow_failure {Err(StringSourceError::CommandFailed(status.code()))?}
+                if *trim {output.truncate(output.trim_end_matches(['\r', '\n']).len());}
+                Some(Cow::Owned(output))
+            },
             Self::ExtractBetween {value, start, end} => {
                 Some(match value.get(job_state)?.ok_or(StringSourceError::StringSourceIsNone)? {
                     Cow::Borrowed(x) => Cow::Borrowed(x
@@ -724,6 +1480,14 @@ impl StringSource {
                 }
                 ret
             },
+            Self::Memoized(source) => {
+                if let Some(ret) = job_state.scratchpad.memos.get(source, job_state.common_args) {
+                    return Ok(ret.map(Cow::Owned));
+                }
+                let ret = source.get(job_state)?.map(Cow::into_owned);
+                job_state.scratchpad.memos.insert(source, job_state.common_args, ret.clone());
+                ret.map(Cow::Owned)
+            },
             Self::Common(common_call) => {
                 job_state.commons.string_sources.get(get_str!(common_call.name, job_state, StringSourceError)).ok_or(StringSourceError::CommonStringSourceNotFound)?.get(&JobStateView {
                     url: job_state.url,
@@ -732,6 +1496,8 @@ impl StringSource {
                     scratchpad: job_state.scratchpad,
                     #[cfg(feature = "cache")]
                     cache: job_state.cache,
+                    #[cfg(feature = "http")]
+                    rate_limiter: job_state.rate_limiter,
                     commons: job_state.commons,
                     common_args: Some(&common_call.args.make(job_state)?),
                     jobs_context: job_state.jobs_context
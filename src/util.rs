@@ -88,6 +88,26 @@ pub(crate) fn neg_vec_keep<I: IntoIterator>(iter: I, start: Option<isize>, end:
     Some(ret.drain(neg_range(start, end, ret.len())?).collect())
 }
 
+/// Gets the substring of `s` from the `start`th [`char`] up to but not including the `end`th [`char`], with negative indices counting from the end.
+///
+/// If `end` is [`None`], slices to the end of `s`.
+pub(crate) fn char_slice(s: &str, start: isize, end: Option<isize>) -> Option<&str> {
+    let len = s.chars().count();
+    let start = neg_range_boundary(start, len)?;
+    let end = match end {
+        Some(end) => neg_range_boundary(end, len)?,
+        None => len
+    };
+    if start > end {None?}
+    let byte_index = |i| s.char_indices().nth(i).map_or(s.len(), |(b, _)| b);
+    s.get(byte_index(start)..byte_index(end))
+}
+
+/// Returns `true` if any of `domain`'s labels start with the punycode ACE prefix `xn--`, case insensitively.
+pub(crate) fn has_punycode_label(domain: &str) -> bool {
+    domain.split('.').any(|label| label.get(..4).is_some_and(|prefix| prefix.eq_ignore_ascii_case("xn--")))
+}
+
 /// Serde helper function.
 pub(crate) const fn get_false() -> bool {false}
 /// Serde helper function.
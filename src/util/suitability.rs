@@ -35,6 +35,8 @@ pub(crate) fn jobs_context_var_is_documented  (name: &StringSource, config: &Con
 /// Helper function to make sure I don't miss documenting anything.
 pub(crate) fn flag_is_documented              (name: &StringSource, config: &Config) {if let StringSource::String(name) = name {assert!(config.docs.flags              .contains_key(name), "Undocumented Flag: {name}")}}
 /// Helper function to make sure I don't miss documenting anything.
+pub(crate) fn flags_are_documented            (names: &HashSet<String>, config: &Config) {for name in names {assert!(config.docs.flags.contains_key(name), "Undocumented Flag: {name}")}}
+/// Helper function to make sure I don't miss documenting anything.
 pub(crate) fn var_is_documented               (name: &StringSource, config: &Config) {if let StringSource::String(name) = name {assert!(config.docs.vars               .contains_key(name), "Undocumented Var: {name}")}}
 /// Helper function to make sure I don't miss documenting anything.
 pub(crate) fn set_is_documented               (name: &StringSource, config: &Config) {if let StringSource::String(name) = name {assert!(config.docs.sets               .contains_key(name), "Undocumented Set: {name}")}}
@@ -0,0 +1,65 @@
+//! Entry points for WebAssembly, for use in things like browser extensions.
+//!
+//! Enabled by the `wasm` feature flag on WebAssembly targets.
+//!
+//! Unlike the CLI, this doesn't use any threads or channels.
+
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use wasm_bindgen::prelude::*;
+
+use crate::types::*;
+
+/// A parsed [`Config`] that can clean any amount of URLs without parsing the config again.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct Cleaner {
+    /// The [`JobsConfig`] to make [`Job`]s with.
+    jobs_config: JobsConfig<'static>
+}
+
+#[wasm_bindgen]
+impl Cleaner {
+    /// Parses `config_json` as a [`Config`].
+    /// # Errors
+    /// If the call to [`serde_json::from_str`] returns an error, that error's message is returned.
+    #[wasm_bindgen(constructor)]
+    pub fn new(config_json: &str) -> Result<Cleaner, JsValue> {
+        let config: Config = serde_json::from_str(config_json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(Self {
+            jobs_config: JobsConfig {
+                config: Cow::Owned(config),
+                #[cfg(feature = "http")]
+                rate_limiter: Default::default()
+            }
+        })
+    }
+
+    /// Cleans `url` and returns the cleaned URL.
+    ///
+    /// The [`Job`] is made with the default [`JobContext`] and [`JobsContext`].
+    /// # Errors
+    /// If the call to [`JobConfig::from_str`] returns an error, that error's message is returned.
+    ///
+    /// If the call to [`Job::do`] returns an error, that error's message is returned.
+    pub fn clean(&self, url: &str) -> Result<String, JsValue> {
+        let job_config = JobConfig::from_str(url).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let jobs_context = JobsContext::default();
+        self.jobs_config.new_job(job_config, &jobs_context).r#do()
+            .map(String::from)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+/// Parses `config_json` as a [`Config`], cleans `url` with it, and returns the cleaned URL.
+///
+/// Parsing the config every call is slow for large configs, so callers cleaning many URLs should make one [`Cleaner`] and use [`Cleaner::clean`] instead.
+/// # Errors
+/// If the call to [`Cleaner::new`] returns an error, that error is returned.
+///
+/// If the call to [`Cleaner::clean`] returns an error, that error is returned.
+#[wasm_bindgen]
+pub fn clean_url(config_json: &str, url: &str) -> Result<String, JsValue> {
+    Cleaner::new(config_json)?.clean(url)
+}